@@ -0,0 +1,6167 @@
+//! An orbit camera controller: the camera circles a target point using pitch, yaw, and roll, and
+//! can zoom, pan, and jump between saved views.
+//!
+//! Add [`CameraControlPlugin`] to the app, then put [`OrbitCamera`] on each camera it should drive.
+//! Other cameras, such as one rendering UI, are left alone. Every orbit camera has its own
+//! [`CameraSettings`], so several can be controlled independently.
+//!
+//! Besides orbiting, a camera can fly around freely or look around from the orbit target in first
+//! person, see [`CameraMode`].
+
+use bevy::{
+    asset::{io::Reader, AssetLoadFailedEvent, AssetLoader, LoadContext},
+    color::palettes::basic::{BLUE, LIME, RED},
+    input::mouse::{AccumulatedMouseMotion, AccumulatedMouseScroll, MouseScrollUnit},
+    math::Mat3A,
+    prelude::*,
+    render::{
+        camera::ScalingMode,
+        mesh::MeshAabb,
+        primitives::Aabb,
+        view::screenshot::{save_to_disk, Screenshot, ScreenshotCaptured},
+    },
+    window::{CursorGrabMode, PrimaryWindow, WindowFocused},
+};
+use rand::{rngs::StdRng, Rng, RngCore, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    f32::consts::{FRAC_PI_2, PI, TAU},
+    fmt, fs,
+    io::ErrorKind,
+    ops::{Add, Mul, Range, Sub},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// File the viewpoint bookmarks are saved to and loaded from.
+const VIEWPOINTS_PATH: &str = "viewpoints.ron";
+
+/// File the recorded camera path is saved to and loaded from.
+const CAMERA_PATH_PATH: &str = "camera_path.ron";
+
+/// Asset with the camera tuning, applied to every orbit camera when it loads or changes.
+const CAMERA_SETTINGS_PATH: &str = "camera_settings.ron";
+
+/// How scroll input zooms the camera.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect, Serialize, Deserialize)]
+pub enum ZoomMode {
+    /// Move the camera toward or away from the orbit target.
+    ///
+    /// Moving an orthographic camera doesn't change how big things look, so with
+    /// `ortho_zoom_scales_view` set its view is scaled along with the distance instead.
+    Dolly,
+    /// Keep the orbit distance fixed and narrow or widen the perspective field of view.
+    Fov,
+}
+
+/// Which kind of projection the camera should use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect, Serialize, Deserialize)]
+pub enum ProjectionMode {
+    Orthographic,
+    Perspective,
+}
+
+/// How an orthographic view keeps the scene framed as the window changes shape.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect, Serialize, Deserialize)]
+pub enum FramingPolicy {
+    /// Show the same width and height whatever the window's shape, stretching the scene to fit.
+    Stretch,
+    /// Keep the height of the view, showing more or less at the sides.
+    KeepHeight,
+    /// Keep the width of the view, showing more or less at the top and bottom.
+    KeepWidth,
+    /// Keep the whole width and height in view, with extra room along whichever axis has spare.
+    Fit,
+}
+
+impl FramingPolicy {
+    /// The scaling mode that frames a view `width` by `height` in world units this way.
+    pub fn scaling_mode(self, width: f32, height: f32) -> ScalingMode {
+        match self {
+            Self::Stretch => ScalingMode::Fixed { width, height },
+            Self::KeepHeight => ScalingMode::FixedVertical {
+                viewport_height: height,
+            },
+            Self::KeepWidth => ScalingMode::FixedHorizontal {
+                viewport_width: width,
+            },
+            Self::Fit => ScalingMode::AutoMin {
+                min_width: width,
+                min_height: height,
+            },
+        }
+    }
+
+    /// Whether `scaling_mode` already frames the view this way.
+    fn matches(self, scaling_mode: &ScalingMode) -> bool {
+        matches!(
+            (self, scaling_mode),
+            (Self::Stretch, ScalingMode::Fixed { .. })
+                | (Self::KeepHeight, ScalingMode::FixedVertical { .. })
+                | (Self::KeepWidth, ScalingMode::FixedHorizontal { .. })
+                | (Self::Fit, ScalingMode::AutoMin { .. })
+        )
+    }
+}
+
+/// How a camera responds to the controls.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect, Serialize, Deserialize)]
+pub enum CameraMode {
+    /// Circle the orbit target, always looking at it.
+    Orbit,
+    /// Fly around freely with the movement keys, looking around with the mouse. The orbit target
+    /// is left where it was.
+    FreeFly,
+    /// Stand at the orbit target and look around from there, like a first-person game.
+    FirstPerson,
+}
+
+/// An in-progress animation of the orbit target from one point to another.
+#[derive(Debug, Clone, Copy, Reflect)]
+pub struct TargetTransition {
+    start: Vec3,
+    desired_target: Vec3,
+    // Start and end camera orientation, when the transition also turns the camera
+    rotation: Option<(Quat, Quat)>,
+    easing: Easing,
+    elapsed: f32,
+}
+
+/// An in-progress morph between orthographic and perspective projection, as a dolly zoom.
+///
+/// Each value is the tangent of half the vertical field of view. `target_tan` is what the full
+/// perspective view has, and the morph blends from `start_tan` to `end_tan`, one of which is it.
+#[derive(Debug, Clone, Copy, Reflect)]
+pub struct ProjectionTransition {
+    start_tan: f32,
+    end_tan: f32,
+    target_tan: f32,
+    elapsed: f32,
+}
+
+/// An in-progress screen shake, started by a [`CameraShake`] event.
+#[derive(Debug, Clone, Copy, Reflect)]
+pub struct ActiveShake {
+    amplitude: f32,
+    duration: f32,
+    elapsed: f32,
+    // Orientation from before this frame's offset was added, restored before the next update
+    unshaken_rotation: Option<Quat>,
+}
+
+/// A saved camera pose, described relative to its orbit target.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct CameraState {
+    pub yaw: f32,
+    pub pitch: f32,
+    pub roll: f32,
+    pub distance: f32,
+    pub target: Vec3,
+}
+
+impl CameraState {
+    /// Capture the pose of a camera orbiting with the given settings.
+    pub fn capture(transform: &Transform, camera_settings: &CameraSettings) -> Self {
+        let (yaw, pitch, roll) = transform.rotation.to_euler(camera_settings.euler_order);
+        Self {
+            yaw,
+            pitch,
+            roll,
+            distance: camera_settings.orbit_distance,
+            target: camera_settings.orbit_target,
+        }
+    }
+
+    /// The orientation of the pose, reading its angles in `order`. This should be the
+    /// `euler_order` of the settings it was captured with.
+    pub fn rotation(&self, order: EulerRot) -> Quat {
+        Quat::from_euler(order, self.yaw, self.pitch, self.roll)
+    }
+
+    /// Whether any part of the pose differs from `other` by more than `epsilon`.
+    fn differs_from(&self, other: &Self, epsilon: f32) -> bool {
+        (self.yaw - other.yaw).abs() > epsilon
+            || (self.pitch - other.pitch).abs() > epsilon
+            || (self.roll - other.roll).abs() > epsilon
+            || (self.distance - other.distance).abs() > epsilon
+            || !self.target.abs_diff_eq(other.target, epsilon)
+    }
+}
+
+/// How an animation's progress speeds up and slows down over its duration.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Reflect, Serialize, Deserialize)]
+pub enum Easing {
+    /// Constant speed throughout.
+    #[default]
+    Linear,
+    /// Start slowly and speed up, along a cubic.
+    EaseIn,
+    /// Start quickly and slow down, along a cubic.
+    EaseOut,
+    /// Start and end slowly, along a cubic.
+    EaseInOut,
+    /// Overshoot the end and spring back, settling with a few decaying wobbles.
+    Elastic,
+    /// Start and end slowly, more gently than `EaseInOut`.
+    Smoothstep,
+}
+
+/// Map progress `t`, from 0 to 1, through `easing`. Every curve starts at 0 and ends at 1, though
+/// `Elastic` goes past 1 on the way.
+pub fn ease(easing: Easing, t: f32) -> f32 {
+    let t = t.clamp(0.0, 1.0);
+    match easing {
+        Easing::Linear => t,
+        Easing::EaseIn => t * t * t,
+        Easing::EaseOut => 1.0 - (1.0 - t).powi(3),
+        Easing::EaseInOut if t < 0.5 => 4.0 * t * t * t,
+        Easing::EaseInOut => 1.0 - (2.0 - 2.0 * t).powi(3) / 2.0,
+        // Pinned at the ends, where the decaying sine only comes close.
+        Easing::Elastic if t == 0.0 || t == 1.0 => t,
+        Easing::Elastic => 2f32.powf(-10.0 * t) * ((t * 10.0 - 0.75) * TAU / 3.0).sin() + 1.0,
+        Easing::Smoothstep => t * t * (3.0 - 2.0 * t),
+    }
+}
+
+/// How playback moves the camera between the keyframes of a [`CameraPath`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Reflect, Serialize, Deserialize)]
+pub enum PathInterpolation {
+    /// Straight from one keyframe to the next, turning sharply at each.
+    #[default]
+    Linear,
+    /// Along a Catmull-Rom spline through the camera positions, curving smoothly past keyframes.
+    CatmullRom,
+}
+
+/// Whether the camera path is being recorded, played back, or neither.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum PathState {
+    #[default]
+    Idle,
+    /// Sampling the camera pose, `elapsed` seconds after recording started.
+    Recording { elapsed: f32 },
+    /// Moving the camera along the keyframes, `elapsed` seconds after playback started.
+    Playing { elapsed: f32 },
+}
+
+/// A recorded camera path, kept between recording and playing it back.
+#[derive(Debug, Resource)]
+pub struct CameraPath {
+    // Camera poses, each with its time in seconds since recording started
+    pub keyframes: Vec<(f32, CameraState)>,
+    // Time between recorded keyframes, in seconds
+    pub sample_interval: f32,
+    pub interpolation: PathInterpolation,
+    pub state: PathState,
+}
+
+impl Default for CameraPath {
+    fn default() -> Self {
+        Self {
+            keyframes: Vec::new(),
+            sample_interval: 0.1,
+            interpolation: PathInterpolation::Linear,
+            state: PathState::Idle,
+        }
+    }
+}
+
+impl CameraPath {
+    /// Length of the recording, in seconds.
+    pub fn duration(&self) -> f32 {
+        self.keyframes.last().map_or(0.0, |(time, _)| *time)
+    }
+
+    /// Camera orientation, distance and target `time` seconds into the recording. Rotation is
+    /// slerped between the keyframes either side, and the rest follows `interpolation`. The
+    /// keyframe angles are read in `order`, the `euler_order` they were recorded with.
+    pub fn sample(&self, time: f32, order: EulerRot) -> Option<(Quat, f32, Vec3)> {
+        let last = self.keyframes.len().checked_sub(1)?;
+        let start = match self
+            .keyframes
+            .iter()
+            .position(|(keyframe_time, _)| *keyframe_time > time)
+        {
+            Some(0) => 0,
+            Some(index) => index - 1,
+            None => last,
+        };
+        let end = (start + 1).min(last);
+
+        let (start_time, start_state) = &self.keyframes[start];
+        let (end_time, end_state) = &self.keyframes[end];
+        let t = if end_time > start_time {
+            ((time - start_time) / (end_time - start_time)).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        let rotation = start_state
+            .rotation(order)
+            .slerp(end_state.rotation(order), t);
+
+        match self.interpolation {
+            PathInterpolation::Linear => Some((
+                rotation,
+                start_state.distance.lerp(end_state.distance, t),
+                start_state.target.lerp(end_state.target, t),
+            )),
+            PathInterpolation::CatmullRom => {
+                // Repeating the first and last keyframes as the outer control points makes the
+                // curve start and end on them.
+                let before = &self.keyframes[start.saturating_sub(1)].1;
+                let after = &self.keyframes[(end + 1).min(last)].1;
+                let [p0, p1, p2, p3] = [before, start_state, end_state, after].map(|state| {
+                    orbit_transform(state.target, state.rotation(order), state.distance).translation
+                });
+                let translation = catmull_rom(p0, p1, p2, p3, t);
+                let distance = catmull_rom(
+                    before.distance,
+                    start_state.distance,
+                    end_state.distance,
+                    after.distance,
+                    t,
+                )
+                .max(0.0);
+                // Put the target in front of the camera, so the orbit pose lands on the spline.
+                let target = translation + rotation * Vec3::NEG_Z * distance;
+                Some((rotation, distance, target))
+            }
+        }
+    }
+}
+
+/// Point `t` of the way, from 0 to 1, along the uniform Catmull-Rom segment from `p1` to `p2`.
+fn catmull_rom<T>(p0: T, p1: T, p2: T, p3: T, t: f32) -> T
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + Mul<f32, Output = T>,
+{
+    let (t2, t3) = (t * t, t * t * t);
+    (p1 * 2.0
+        + (p2 - p0) * t
+        + (p0 * 2.0 - p1 * 5.0 + p2 * 4.0 - p3) * t2
+        + (p1 * 3.0 - p0 - p2 * 3.0 + p3) * t3)
+        * 0.5
+}
+
+/// Sent when an orbit camera's pose changes, so other systems can react to the camera moving.
+#[derive(Debug, Clone, Copy, Event)]
+pub struct CameraMoved {
+    pub camera: Entity,
+    pub yaw: f32,
+    pub pitch: f32,
+    pub roll: f32,
+    pub distance: f32,
+    pub target: Vec3,
+}
+
+/// Send to shake every orbit camera, for example as feedback for an impact.
+///
+/// The shake turns the view by random offsets of up to `amplitude` radians, fading out over
+/// `duration` seconds. It is layered on top of the orbit pose, which it leaves untouched.
+#[derive(Debug, Clone, Copy, Event)]
+pub struct CameraShake {
+    pub amplitude: f32,
+    pub duration: f32,
+}
+
+/// Send to frame every mesh in the scene with every orbit camera, like pressing the `frame_all`
+/// binding. Useful once a scene has loaded, for example.
+#[derive(Debug, Default, Clone, Copy, Event)]
+pub struct FrameAll;
+
+/// Trigger on an entity, with `commands.trigger_targets(FocusCamera, entity)`, to have every orbit
+/// camera animate its target to the entity and zoom so it fills the view, like double-clicking it.
+#[derive(Debug, Default, Clone, Copy, Event)]
+pub struct FocusCamera;
+
+/// Camera poses bookmarked on the number keys 1 to 9.
+#[derive(Debug, Default, Resource, Serialize, Deserialize)]
+pub struct Viewpoints {
+    pub slots: [Option<CameraState>; 9],
+}
+
+/// Marks an entity the focus cycle binding steps through. Without any, named meshes are used.
+#[derive(Debug, Clone, Copy, Default, Component)]
+pub struct Focusable;
+
+/// The only orbit camera that responds to orbit, pan and zoom input, when inserted. The others
+/// hold their pose. Without it, every orbit camera takes input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Resource)]
+pub struct ActiveCamera(pub Entity);
+
+/// Where the focus cycle binding last moved the orbit target, and which entity was last focused on
+/// by it or by double-clicking.
+#[derive(Debug, Default, Resource)]
+pub struct FocusCycle {
+    pub focused: Option<Entity>,
+    // Position of the focused entity in the cycle, ordered by entity
+    pub index: Option<usize>,
+}
+
+/// A mouse button or keyboard key that a camera action can be bound to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InputButton {
+    Mouse(MouseButton),
+    Key(KeyCode),
+}
+
+/// A button that triggers a camera action, optionally only while a modifier key is also held.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Binding {
+    pub button: InputButton,
+    pub modifier: Option<KeyCode>,
+}
+
+impl Binding {
+    pub const fn mouse(button: MouseButton) -> Self {
+        Self {
+            button: InputButton::Mouse(button),
+            modifier: None,
+        }
+    }
+
+    pub const fn key(key: KeyCode) -> Self {
+        Self {
+            button: InputButton::Key(key),
+            modifier: None,
+        }
+    }
+
+    /// The same binding, but only active while `modifier` is held too.
+    pub const fn with_modifier(self, modifier: KeyCode) -> Self {
+        Self {
+            modifier: Some(modifier),
+            ..self
+        }
+    }
+
+    fn modifier_held(&self, keys: &ButtonInput<KeyCode>) -> bool {
+        self.modifier.is_none_or(|modifier| keys.pressed(modifier))
+    }
+
+    /// Whether the button is held, whether or not the modifier still is.
+    fn button_pressed(
+        &self,
+        mouse_buttons: &ButtonInput<MouseButton>,
+        keys: &ButtonInput<KeyCode>,
+    ) -> bool {
+        match self.button {
+            InputButton::Mouse(button) => mouse_buttons.pressed(button),
+            InputButton::Key(key) => keys.pressed(key),
+        }
+    }
+
+    /// Whether the button is held, along with the modifier if there is one.
+    pub fn pressed(
+        &self,
+        mouse_buttons: &ButtonInput<MouseButton>,
+        keys: &ButtonInput<KeyCode>,
+    ) -> bool {
+        self.button_pressed(mouse_buttons, keys) && self.modifier_held(keys)
+    }
+
+    /// Whether the button went down this frame, with the modifier already held if there is one.
+    pub fn just_pressed(
+        &self,
+        mouse_buttons: &ButtonInput<MouseButton>,
+        keys: &ButtonInput<KeyCode>,
+    ) -> bool {
+        let just_pressed = match self.button {
+            InputButton::Mouse(button) => mouse_buttons.just_pressed(button),
+            InputButton::Key(key) => keys.just_pressed(key),
+        };
+        just_pressed && self.modifier_held(keys)
+    }
+}
+
+/// Mouse conventions of popular 3D tools, to set up [`KeyBindings`] the way users expect.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NavScheme {
+    /// Left mouse orbits, right mouse rolls and middle mouse pans.
+    #[default]
+    Default,
+    /// Alt with the left mouse orbits, with the middle mouse pans and with the right mouse zooms.
+    Maya,
+    /// Middle mouse orbits, Shift with it pans and Ctrl with it zooms.
+    Blender,
+    /// Alt with the left mouse orbits, middle mouse pans and Alt with the right mouse zooms.
+    Unity,
+    /// Everything on one button, for users who can't hold several at once. Left mouse orbits, and
+    /// pressing Z switches it to zooming with vertical motion until pressed again. The arrow keys
+    /// pan.
+    OneButton,
+}
+
+/// Which buttons and keys drive each camera action. Insert a customized copy before adding
+/// [`CameraControlPlugin`], or change the resource at runtime, to remap the controls.
+#[derive(Debug, Clone, Resource)]
+pub struct KeyBindings {
+    // Held while moving the mouse to orbit, roll, pan or zoom. Scrolling always zooms as well.
+    pub orbit: Binding,
+    pub roll: Option<Binding>,
+    pub pan: Binding,
+    pub zoom: Option<Binding>,
+    // Pressed to switch the orbit drag between orbiting and zooming
+    pub toggle_drag_zoom: Option<Binding>,
+    // Dragged to draw a box, framing the meshes that end up inside it
+    pub box_select: Option<Binding>,
+    // Held to pan with the keyboard
+    pub pan_left: KeyCode,
+    pub pan_right: KeyCode,
+    pub pan_up: KeyCode,
+    pub pan_down: KeyCode,
+    // Held to orbit with the keyboard
+    pub orbit_left: KeyCode,
+    pub orbit_right: KeyCode,
+    pub orbit_up: KeyCode,
+    pub orbit_down: KeyCode,
+    // Held to slow rotation and zoom down by precision_factor
+    pub precision: KeyCode,
+    // Held while orbiting to snap yaw to multiples of snap_angle
+    pub snap: KeyCode,
+    // Double-clicked to focus on the object under the cursor
+    pub focus: MouseButton,
+    pub reset: Binding,
+    pub toggle_projection: Binding,
+    pub toggle_axes: Binding,
+    pub toggle_hud: Binding,
+    pub frame_all: Binding,
+    // Frame just the entity last focused on
+    pub frame_focused: Binding,
+    // Step the orbit target through focusable entities, forward or back
+    pub focus_next: Binding,
+    pub focus_previous: Binding,
+    pub top_view: Binding,
+    pub front_view: Binding,
+    pub right_view: Binding,
+    // Keys that recall the viewpoint in each slot, or store it while a store modifier is held
+    pub viewpoint_slots: [KeyCode; 9],
+    pub store_viewpoint_modifiers: Vec<KeyCode>,
+    // Writes the viewpoints to disk
+    pub save_viewpoints: Binding,
+    // Start or stop recording the camera path, and playing it back
+    pub toggle_recording: Binding,
+    pub toggle_playback: Binding,
+    // Writes the camera path to disk
+    pub save_path: Binding,
+    // Any of these saves a screenshot of the window
+    pub screenshot: Vec<Binding>,
+    // Switch between orbiting, flying and standing at the target
+    pub toggle_mode: Binding,
+    pub toggle_first_person: Binding,
+    // Make the next orbit camera the `ActiveCamera`
+    pub next_camera: Binding,
+    // Move the near or far clipping plane closer to the camera or further away
+    pub near_in: Binding,
+    pub near_out: Binding,
+    pub far_in: Binding,
+    pub far_out: Binding,
+    // Held to fly in `CameraMode::FreeFly`
+    pub fly_forward: KeyCode,
+    pub fly_back: KeyCode,
+    pub fly_left: KeyCode,
+    pub fly_right: KeyCode,
+    pub fly_up: KeyCode,
+    pub fly_down: KeyCode,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            orbit: Binding::mouse(MouseButton::Left),
+            roll: Some(Binding::mouse(MouseButton::Right)),
+            pan: Binding::mouse(MouseButton::Middle),
+            zoom: None,
+            toggle_drag_zoom: None,
+            box_select: Some(Binding::mouse(MouseButton::Left).with_modifier(KeyCode::AltLeft)),
+            pan_left: KeyCode::ArrowLeft,
+            pan_right: KeyCode::ArrowRight,
+            pan_up: KeyCode::ArrowUp,
+            pan_down: KeyCode::ArrowDown,
+            orbit_left: KeyCode::Numpad4,
+            orbit_right: KeyCode::Numpad6,
+            orbit_up: KeyCode::Numpad8,
+            orbit_down: KeyCode::Numpad2,
+            precision: KeyCode::ShiftLeft,
+            snap: KeyCode::ControlLeft,
+            focus: MouseButton::Left,
+            reset: Binding::key(KeyCode::KeyR),
+            toggle_projection: Binding::key(KeyCode::KeyP),
+            toggle_axes: Binding::key(KeyCode::KeyG),
+            toggle_hud: Binding::key(KeyCode::F3),
+            frame_all: Binding::key(KeyCode::KeyF),
+            frame_focused: Binding::key(KeyCode::Period),
+            focus_next: Binding::key(KeyCode::Tab),
+            focus_previous: Binding::key(KeyCode::Tab).with_modifier(KeyCode::ShiftLeft),
+            top_view: Binding::key(KeyCode::Numpad7),
+            front_view: Binding::key(KeyCode::Numpad1),
+            right_view: Binding::key(KeyCode::Numpad3),
+            viewpoint_slots: [
+                KeyCode::Digit1,
+                KeyCode::Digit2,
+                KeyCode::Digit3,
+                KeyCode::Digit4,
+                KeyCode::Digit5,
+                KeyCode::Digit6,
+                KeyCode::Digit7,
+                KeyCode::Digit8,
+                KeyCode::Digit9,
+            ],
+            store_viewpoint_modifiers: vec![KeyCode::ControlLeft, KeyCode::ControlRight],
+            save_viewpoints: Binding::key(KeyCode::F5),
+            toggle_recording: Binding::key(KeyCode::F6),
+            toggle_playback: Binding::key(KeyCode::F7),
+            save_path: Binding::key(KeyCode::F8),
+            screenshot: vec![
+                Binding::key(KeyCode::PrintScreen),
+                Binding::key(KeyCode::F12),
+            ],
+            toggle_mode: Binding::key(KeyCode::KeyC),
+            toggle_first_person: Binding::key(KeyCode::KeyV),
+            next_camera: Binding::key(KeyCode::Backquote),
+            near_in: Binding::key(KeyCode::Minus),
+            near_out: Binding::key(KeyCode::Equal),
+            far_in: Binding::key(KeyCode::BracketLeft),
+            far_out: Binding::key(KeyCode::BracketRight),
+            fly_forward: KeyCode::KeyW,
+            fly_back: KeyCode::KeyS,
+            fly_left: KeyCode::KeyA,
+            fly_right: KeyCode::KeyD,
+            fly_up: KeyCode::KeyE,
+            fly_down: KeyCode::KeyQ,
+        }
+    }
+}
+
+impl KeyBindings {
+    /// The default bindings, with orbit, roll, pan and zoom set up for `scheme`.
+    pub fn for_scheme(scheme: NavScheme) -> Self {
+        let mut bindings = Self::default();
+        bindings.apply_scheme(scheme);
+        bindings
+    }
+
+    /// Switch the mouse navigation over to `scheme`, keeping every other binding as it is.
+    pub fn apply_scheme(&mut self, scheme: NavScheme) {
+        use KeyCode::{AltLeft, ControlLeft, ShiftLeft};
+        use MouseButton::{Left, Middle, Right};
+
+        let mouse = Binding::mouse;
+        self.toggle_drag_zoom = None;
+        // Box selection moves to whichever button and modifier the scheme leaves free.
+        self.box_select = match scheme {
+            NavScheme::Default => Some(mouse(Left).with_modifier(AltLeft)),
+            NavScheme::Maya | NavScheme::Unity => Some(mouse(Left).with_modifier(ShiftLeft)),
+            NavScheme::Blender => Some(mouse(Left)),
+            NavScheme::OneButton => None,
+        };
+        (self.orbit, self.roll, self.pan, self.zoom) = match scheme {
+            NavScheme::Default => (mouse(Left), Some(mouse(Right)), mouse(Middle), None),
+            NavScheme::Maya => (
+                mouse(Left).with_modifier(AltLeft),
+                None,
+                mouse(Middle).with_modifier(AltLeft),
+                Some(mouse(Right).with_modifier(AltLeft)),
+            ),
+            NavScheme::Blender => (
+                mouse(Middle),
+                None,
+                mouse(Middle).with_modifier(ShiftLeft),
+                Some(mouse(Middle).with_modifier(ControlLeft)),
+            ),
+            NavScheme::Unity => (
+                mouse(Left).with_modifier(AltLeft),
+                None,
+                mouse(Middle),
+                Some(mouse(Right).with_modifier(AltLeft)),
+            ),
+            NavScheme::OneButton => {
+                self.toggle_drag_zoom = Some(Binding::key(KeyCode::KeyZ));
+                (mouse(Left), None, mouse(Middle), None)
+            }
+        };
+    }
+
+    /// Whether a mouse drag `binding` is held.
+    ///
+    /// Drag bindings can share a button and differ only by modifier, like Blender's middle mouse
+    /// orbit and Shift+middle mouse pan. The one whose modifier is held wins, so a binding without
+    /// a modifier gives way while another one on the same button is active.
+    pub fn drag_pressed(
+        &self,
+        binding: &Binding,
+        mouse_buttons: &ButtonInput<MouseButton>,
+        keys: &ButtonInput<KeyCode>,
+    ) -> bool {
+        if !binding.pressed(mouse_buttons, keys) {
+            return false;
+        }
+        if binding.modifier.is_some() {
+            return true;
+        }
+        ![
+            Some(self.orbit),
+            self.roll,
+            Some(self.pan),
+            self.zoom,
+            self.box_select,
+        ]
+        .into_iter()
+        .flatten()
+        .any(|other| {
+            other.button == binding.button
+                && other.modifier.is_some()
+                && other.pressed(mouse_buttons, keys)
+        })
+    }
+}
+
+/// Marks the text node that `camera_hud` writes camera readouts into. The plugin spawns one in the
+/// bottom left corner.
+#[derive(Debug, Default, Clone, Copy, Component)]
+pub struct CameraHud;
+
+/// Marks the node drawn as the rectangle while dragging the box selection binding. The plugin
+/// spawns one, hidden until a box is dragged.
+#[derive(Debug, Default, Clone, Copy, Component)]
+pub struct SelectionBox;
+
+/// Marks a node to hide while `screenshot` captures the window, such as help text. The camera HUD
+/// has it already.
+#[derive(Debug, Default, Clone, Copy, Component)]
+pub struct HideInScreenshot;
+
+/// The visibility a `HideInScreenshot` node had before it was hidden for a screenshot.
+#[derive(Component)]
+struct HiddenForScreenshot(Visibility);
+
+/// Marks a camera to be driven by the orbit controls. Cameras without it are left alone.
+#[derive(Debug, Default, Clone, Copy, Component)]
+#[require(CameraSettings)]
+pub struct OrbitCamera;
+
+/// Tuning for the orbit camera, along with the state it carries between frames.
+///
+/// Serialized fields are the tuning that can be loaded from `camera_settings.ron`, with any left
+/// out taking their default values. The state carried between frames is skipped.
+#[derive(Debug, Clone, Component, Reflect, Serialize, Deserialize)]
+#[reflect(Component)]
+#[serde(default)]
+pub struct CameraSettings {
+    // Point the camera orbits around and looks at. Orbiting, zooming, panning, focusing and
+    // following all place the camera relative to, or move, this one point.
+    pub orbit_target: Vec3,
+    // Keep the orbit target inside this box, given as (min, max) corners
+    pub target_bounds: Option<(Vec3, Vec3)>,
+    // Animation currently moving the orbit target, if any
+    #[serde(skip)]
+    pub target_transition: Option<TargetTransition>,
+    // How long it takes to animate the orbit target to a new point, in seconds
+    pub transition_duration: f32,
+    // Accessibility setting for motion-sensitive users: turn off shake, inertia, the turntable and
+    // banking, and cut transitions short with no overshoot, whatever their own settings say
+    pub reduce_motion: bool,
+    // Orientation the camera starts in, and that resetting the view turns it back to, in
+    // euler_order
+    pub initial_yaw: f32,
+    pub initial_pitch: f32,
+    pub initial_roll: f32,
+    // Orbit target and distance resetting the view returns to, set along with the starting ones
+    // by `start_at`
+    pub initial_target: Vec3,
+    pub initial_distance: f32,
+    // Frame the whole scene once it has spawned, starting there instead of at the orbit target
+    // and distance given
+    pub fit_on_startup: bool,
+    // Order the yaw, pitch and roll angles are applied in, everywhere the camera's orientation is
+    // read or written as angles: saved views, camera paths and the initial orientation. Angles
+    // saved in one order come back wrong in another.
+    pub euler_order: EulerRot,
+    // Easing for animations that focus on something, that reset the view, that jump to a preset
+    // or saved view, and for camera path playback
+    pub focus_easing: Easing,
+    pub reset_easing: Easing,
+    pub preset_easing: Easing,
+    pub playback_easing: Easing,
+    pub orbit_distance: f32,
+    // Clamp orbit distance to this range
+    pub distance_range: Range<f32>,
+    // Distance that orbit_distance is smoothly moved toward
+    pub target_distance: f32,
+    // Rate orbit_distance is changing at while it settles on target_distance
+    #[serde(skip)]
+    pub distance_velocity: f32,
+    // Point under the cursor when zooming started, kept fixed on screen while the zoom settles
+    #[serde(skip)]
+    pub zoom_anchor: Option<Vec3>,
+    pub pitch_speed: f32,
+    // Clamp pitch to this range
+    pub pitch_range: Range<f32>,
+    // Let pitch carry on over the top and bottom instead, tumbling the camera upside down
+    pub allow_full_pitch: bool,
+    // Clamp yaw to the arc turning counterclockwise, seen from above, from start to end. The arc
+    // may cross ±π, and an end below the start wraps through it, so 300°..60° is the 120° arc
+    // through 0. Yaw turns all the way round when None.
+    pub yaw_range: Option<Range<f32>>,
+    // Width of the zone inside each end of pitch_range where pitching slows to a stop. 0 makes the
+    // limits a hard stop.
+    pub soft_pitch_margin: f32,
+    pub roll_speed: f32,
+    pub yaw_speed: f32,
+    // Flip the direction mouse motion rotates the camera
+    pub invert_pitch: bool,
+    pub invert_yaw: bool,
+    // Flip the zoom direction of scrolling, for trackpads with "natural" scrolling
+    pub invert_scroll: bool,
+    // Zoom steps per line scrolled on a mouse wheel, and per pixel scrolled on a trackpad
+    pub scroll_line_scale: f32,
+    pub scroll_pixel_scale: f32,
+    pub projection_mode: ProjectionMode,
+    // How long, in seconds, switching projection takes to morph from one to the other, see
+    // `morph_projection`. 0 switches at once
+    pub projection_transition_time: f32,
+    // Morph currently switching the projection, if any
+    #[serde(skip)]
+    pub projection_transition: Option<ProjectionTransition>,
+    // How an orthographic projection's view adapts to the window's shape. A projection set up
+    // another way is switched over to it
+    pub framing_policy: FramingPolicy,
+    pub zoom_mode: ZoomMode,
+    // While the projection is orthographic, scale the view in proportion to the orbit distance so
+    // zooming shows, and zoom that way in `ZoomMode::Fov` too. See `effective_zoom_mode`
+    pub ortho_zoom_scales_view: bool,
+    // Orbit distance the orthographic view was last scaled for
+    #[serde(skip)]
+    pub ortho_zoom_distance: Option<f32>,
+    pub zoom_speed: f32,
+    // Roughly how long, in seconds, turning, zooming and panning each take to catch up with input.
+    // 0 follows input exactly.
+    pub rotate_smoothing: f32,
+    pub zoom_smoothing: f32,
+    pub pan_smoothing: f32,
+    // Radians of field of view per scroll step in `ZoomMode::Fov`
+    pub fov_zoom_speed: f32,
+    // Clamp field of view to this range
+    pub fov_range: Range<f32>,
+    // Clipping planes of the camera's projection, perspective or orthographic. Framing the whole
+    // scene moves far to suit its size.
+    pub near: f32,
+    pub far: f32,
+    // How far the scene moves per pixel dragged while panning, as a share of that pixel's size at
+    // the orbit target. 1 keeps the target point under the cursor.
+    pub pan_speed: f32,
+    // Fraction of the distance zoomed per pixel of vertical mouse motion with the zoom binding held
+    pub drag_zoom_speed: f32,
+    // Whether the orbit drag zooms instead, switched by the toggle_drag_zoom binding
+    #[serde(skip)]
+    pub drag_zoom_toggled: bool,
+    // Pan distance per second while an arrow key is held, per unit of orbit distance
+    pub key_pan_speed: f32,
+    // Multiplier applied to rotation and zoom speeds while left shift is held
+    pub precision_factor: f32,
+    // Yaw step, in radians, that orbiting snaps to while the snap key is held. 0 turns snapping
+    // off.
+    pub snap_angle: f32,
+    // Yaw the drag would have reached without snapping, while snapping
+    #[serde(skip)]
+    pub snap_yaw: Option<f32>,
+    // Keep rotating after the orbit drag is released, slowing down over time
+    pub enable_inertia: bool,
+    // Rate at which the orbit momentum decays once released, per second
+    pub inertia_damping: f32,
+    // Angular velocity of the orbit momentum, in radians per second
+    #[serde(skip)]
+    pub yaw_velocity: f32,
+    #[serde(skip)]
+    pub pitch_velocity: f32,
+    // Longest gap between two clicks that still counts as a double-click, in seconds
+    pub double_click_time: f32,
+    // Yaw, pitch and roll set through `look_from_angles`, applied on the next orbit update
+    #[serde(skip)]
+    pub requested_angles: Option<(f32, f32, f32)>,
+    // Turning still to be eased in as (yaw, pitch, roll), and the rate it is being applied at
+    #[serde(skip)]
+    pub pending_rotation: Vec3,
+    #[serde(skip)]
+    pub rotation_velocity: Vec3,
+    // Pan still to be eased into the orbit target, and the rate it is being applied at
+    #[serde(skip)]
+    pub pending_pan: Vec3,
+    #[serde(skip)]
+    pub pan_velocity: Vec3,
+    // Radians per second at full right-stick deflection
+    pub gamepad_yaw_speed: f32,
+    pub gamepad_pitch_speed: f32,
+    // Distance per second with a trigger fully pressed
+    pub gamepad_zoom_speed: f32,
+    // Stick deflections shorter than this are ignored, so a resting stick doesn't drift
+    pub gamepad_deadzone: f32,
+    // Exponent shaping the stick response past the deadzone. 1 is linear, and higher values give
+    // finer control near the center while still reaching full speed at the edge.
+    pub gamepad_curve: f32,
+    // Radians per second once an orbit key has been held for key_attack_time seconds. Releasing it
+    // slows back to a stop over key_release_time seconds.
+    pub key_yaw_speed: f32,
+    pub key_pitch_speed: f32,
+    pub key_attack_time: f32,
+    pub key_release_time: f32,
+    // How far each orbit key axis has ramped towards full speed, from -1 to 1 for yaw and pitch
+    #[serde(skip)]
+    pub key_ramp: Vec2,
+    // Yaw, pitch and roll deltas from touch gestures, added on the next orbit update
+    #[serde(skip)]
+    pub queued_rotation: Vec3,
+    // Slowly orbit the target once there has been no input for idle_timeout seconds
+    pub enable_turntable: bool,
+    // Turns per minute of the idle orbit, about turntable_axis through the orbit target
+    pub turntable_rpm: f32,
+    pub turntable_axis: Vec3,
+    pub idle_timeout: f32,
+    // Seconds since the last input
+    #[serde(skip)]
+    pub idle_time: f32,
+    // Screen shake currently playing, if any
+    #[serde(skip)]
+    pub shake: Option<ActiveShake>,
+    // Seed for the random effects, which the `CameraRng` takes from the first orbit camera
+    pub rng_seed: u64,
+    // Gentle drift of the view while there is no input, in radians of yaw and pitch at most, and
+    // its speed in cycles per second. An amplitude of 0 turns it off.
+    pub sway_amplitude: f32,
+    pub sway_frequency: f32,
+    // Random phases of the sway's layered waves, drawn from `CameraRng` when the sway starts
+    #[serde(skip)]
+    pub sway_phases: Option<[f32; 4]>,
+    // How much of the sway is showing, easing to 1 while idle and 0 during input
+    #[serde(skip)]
+    pub sway_weight: f32,
+    // Orientation from before this frame's sway, restored before the next update
+    #[serde(skip)]
+    pub unswayed_rotation: Option<Quat>,
+    // Draw the X, Y and Z axes at the orbit target, toggled with G
+    pub show_axes: bool,
+    // Color of the marker drawn at the orbit target while orbiting or panning
+    pub focus_gizmo_color: Color,
+    // How long the marker takes to fade out once the camera is let go, in seconds
+    pub focus_gizmo_fade: f32,
+    // Seconds since the camera was last orbited or panned
+    #[serde(skip)]
+    pub focus_gizmo_age: f32,
+    // Outline the mesh under the cursor, the one double-clicking would focus on
+    pub highlight_hover: bool,
+    pub hover_color: Color,
+    // Mesh currently under the cursor, while highlight_hover is set
+    #[serde(skip)]
+    pub hovered: Option<Entity>,
+    // How far short of an obstruction the camera stops when geometry would block its view
+    pub collision_margin: f32,
+    // Stiffness of the spring that eases the camera back out once an obstruction clears
+    pub collision_spring_stiffness: f32,
+    // Distance the camera is held at while it is pulled in by, or recovering from, an obstruction
+    #[serde(skip)]
+    pub collision_distance: Option<f32>,
+    #[serde(skip)]
+    pub collision_velocity: f32,
+    // Lowest height the camera may go to, so it can't dip below the ground
+    pub min_camera_height: Option<f32>,
+    // How far above the scene's geometry below it the camera must stay, so it rides over uneven
+    // ground. None lets it go anywhere
+    pub ground_margin: Option<f32>,
+    // glTF file, relative to the assets folder, for an app to show instead of its own scene. The
+    // plugin itself doesn't load it.
+    pub scene_path: Option<String>,
+    // Time constant of the low-pass filter on orbit mouse motion, in seconds. 0 turns it off.
+    pub mouse_smoothing: f32,
+    // Filtered orbit mouse motion, in pixels per second
+    #[serde(skip)]
+    pub smoothed_mouse_motion: Vec2,
+    // Scale pitch_speed and yaw_speed with the orbit distance, see `rotation_speeds`
+    pub sensitivity_scales_with_distance: bool,
+    // Orbit distance at which the scaled speeds equal pitch_speed and yaw_speed
+    pub sensitivity_reference_distance: f32,
+    // How strongly each axis follows the distance: 1 is linear, 0.5 is the square root
+    pub pitch_distance_exponent: f32,
+    pub yaw_distance_exponent: f32,
+    // Scale mouse and touch orbiting so the scene around the target moves the same distance on
+    // screen per pixel dragged, whatever the zoom, see `screen_speed_scale`. Takes the place of
+    // `sensitivity_scales_with_distance` for those inputs
+    pub constant_screen_speed: bool,
+    // Ease roll back to level whenever there is no roll input
+    pub auto_level_roll: bool,
+    // Keep the camera level at all times, ignoring roll input, so the roll binding does nothing
+    pub lock_roll: bool,
+    // Rate at which roll returns to level, per second
+    pub roll_return_speed: f32,
+    // Roll into fast yaw like a banking aircraft, by this many radians per radian per second of
+    // yaw. 0 turns banking off.
+    pub bank_factor: f32,
+    // Largest bank angle, in radians
+    pub max_bank_angle: f32,
+    // Roughly how long, in seconds, banking takes to follow changes in yaw speed
+    pub bank_smooth_time: f32,
+    // Bank currently added on top of the manual roll, and the rate it is changing at
+    #[serde(skip)]
+    pub bank_roll: f32,
+    #[serde(skip)]
+    pub bank_velocity: f32,
+    pub mode: CameraMode,
+    // Turn the camera back toward the orbit target whenever it strays within onscreen_margin of
+    // the edge of the view, or behind it. An orbiting camera keeps it in the middle anyway, bar
+    // effects, so this mostly matters when flying
+    pub keep_target_onscreen: bool,
+    // Share of the viewport's width and height, in from each edge, that the target is kept out of
+    pub onscreen_margin: f32,
+    // Roughly how long, in seconds, turning back toward the target takes
+    pub onscreen_smooth_time: f32,
+    // Flying speed in `CameraMode::FreeFly`, in units per second
+    pub fly_speed: f32,
+    // Ignore mouse orbiting, panning and zooming while the cursor is over interactive UI
+    pub block_input_over_ui: bool,
+    // Only take input while the cursor is over this camera's viewport, or for the rest of a drag
+    // that started there. Lets cameras split the window between them.
+    pub viewport_input: bool,
+    // Whether the camera responds to orbit, pan and zoom input this frame, worked out by
+    // `route_input` from viewport_input and the `ActiveCamera`
+    #[serde(skip)]
+    pub receives_input: bool,
+    // List this camera's angles, distance and target in the corner of the screen
+    pub show_hud: bool,
+    // Hide the cursor and keep it in place while dragging to orbit, roll, pan or zoom
+    pub lock_cursor_while_dragging: bool,
+    // Turn toward a window edge while the cursor rests within edge_scroll_margin_px of it
+    pub edge_scroll_enabled: bool,
+    pub edge_scroll_margin_px: f32,
+    // Radians per second with the cursor at the very edge
+    pub edge_scroll_speed: f32,
+    // Entity whose position the orbit target tracks, turning the orbit camera into a chase camera.
+    // The focus cycle sets it too, and panning or animating the target elsewhere clears it.
+    #[serde(skip)]
+    pub follow_target: Option<Entity>,
+    // Roughly how long, in seconds, the orbit target takes to catch up with the followed entity. 0
+    // tracks it exactly.
+    pub follow_smooth_time: f32,
+    // Rate the orbit target is moving at while it catches up
+    #[serde(skip)]
+    pub follow_velocity: Vec3,
+    // Where the followed entity was last seen
+    #[serde(skip)]
+    pub follow_position: Option<Vec3>,
+}
+
+impl Default for CameraSettings {
+    fn default() -> Self {
+        // Limiting pitch stops some unexpected rotation past 90° up or down.
+        let pitch_limit = FRAC_PI_2 - 0.01;
+        Self {
+            // These values are completely arbitrary, chosen because they seem to produce
+            // "sensible" results for this example. Adjust as required.
+            orbit_target: Vec3::ZERO,
+            target_bounds: None,
+            target_transition: None,
+            transition_duration: 0.6,
+            reduce_motion: false,
+            // Looking at the target from (10, 12, 16).
+            initial_yaw: 10f32.atan2(16.0),
+            initial_pitch: -(12f32 / 500f32.sqrt()).asin(),
+            initial_roll: 0.0,
+            initial_target: Vec3::ZERO,
+            initial_distance: 20.0,
+            fit_on_startup: true,
+            euler_order: EulerRot::YXZ,
+            focus_easing: Easing::Smoothstep,
+            reset_easing: Easing::Smoothstep,
+            preset_easing: Easing::Smoothstep,
+            playback_easing: Easing::Linear,
+            orbit_distance: 20.0,
+            distance_range: 2.0..80.0,
+            target_distance: 20.0,
+            distance_velocity: 0.0,
+            zoom_anchor: None,
+            pitch_speed: 0.003,
+            pitch_range: -pitch_limit..pitch_limit,
+            allow_full_pitch: false,
+            yaw_range: None,
+            soft_pitch_margin: 0.0,
+            roll_speed: 1.0,
+            yaw_speed: 0.004,
+            invert_pitch: false,
+            invert_yaw: false,
+            invert_scroll: false,
+            scroll_line_scale: 1.0,
+            // A wheel notch is about 20 pixels of trackpad scrolling.
+            scroll_pixel_scale: 0.05,
+            projection_mode: ProjectionMode::Orthographic,
+            projection_transition_time: 0.4,
+            projection_transition: None,
+            framing_policy: FramingPolicy::Fit,
+            zoom_mode: ZoomMode::Dolly,
+            ortho_zoom_scales_view: true,
+            ortho_zoom_distance: None,
+            zoom_speed: 1.0,
+            rotate_smoothing: 0.1,
+            zoom_smoothing: 0.1,
+            pan_smoothing: 0.1,
+            fov_zoom_speed: 0.05,
+            fov_range: 0.1..2.8,
+            near: 0.1,
+            far: 1000.0,
+            pan_speed: 1.0,
+            drag_zoom_speed: 0.005,
+            drag_zoom_toggled: false,
+            key_pan_speed: 0.2,
+            snap_angle: 15f32.to_radians(),
+            snap_yaw: None,
+            precision_factor: 0.25,
+            enable_inertia: true,
+            inertia_damping: 4.0,
+            yaw_velocity: 0.0,
+            pitch_velocity: 0.0,
+            double_click_time: 0.3,
+            requested_angles: None,
+            pending_rotation: Vec3::ZERO,
+            rotation_velocity: Vec3::ZERO,
+            pending_pan: Vec3::ZERO,
+            pan_velocity: Vec3::ZERO,
+            gamepad_yaw_speed: 2.0,
+            gamepad_pitch_speed: 1.5,
+            gamepad_zoom_speed: 10.0,
+            gamepad_deadzone: 0.15,
+            gamepad_curve: 1.0,
+            key_yaw_speed: 2.0,
+            key_pitch_speed: 1.5,
+            key_attack_time: 0.15,
+            key_release_time: 0.2,
+            key_ramp: Vec2::ZERO,
+            queued_rotation: Vec3::ZERO,
+            enable_turntable: false,
+            turntable_rpm: 2.0,
+            turntable_axis: Vec3::Y,
+            idle_timeout: 5.0,
+            idle_time: 0.0,
+            shake: None,
+            rng_seed: 0x5EED,
+            sway_amplitude: 0.0,
+            sway_frequency: 0.2,
+            sway_phases: None,
+            sway_weight: 0.0,
+            unswayed_rotation: None,
+            show_axes: false,
+            focus_gizmo_color: Color::WHITE,
+            focus_gizmo_fade: 0.5,
+            focus_gizmo_age: f32::INFINITY,
+            highlight_hover: false,
+            hover_color: Color::srgb(1.0, 0.8, 0.2),
+            hovered: None,
+            collision_margin: 0.2,
+            collision_spring_stiffness: 50.0,
+            collision_distance: None,
+            collision_velocity: 0.0,
+            min_camera_height: None,
+            ground_margin: None,
+            keep_target_onscreen: false,
+            onscreen_margin: 0.1,
+            onscreen_smooth_time: 0.3,
+            scene_path: None,
+            mouse_smoothing: 0.0,
+            smoothed_mouse_motion: Vec2::ZERO,
+            sensitivity_scales_with_distance: false,
+            sensitivity_reference_distance: 20.0,
+            constant_screen_speed: false,
+            pitch_distance_exponent: 0.5,
+            yaw_distance_exponent: 0.5,
+            auto_level_roll: false,
+            lock_roll: false,
+            roll_return_speed: 3.0,
+            bank_factor: 0.0,
+            max_bank_angle: 15f32.to_radians(),
+            bank_smooth_time: 0.25,
+            bank_roll: 0.0,
+            bank_velocity: 0.0,
+            mode: CameraMode::Orbit,
+            fly_speed: 5.0,
+            block_input_over_ui: true,
+            viewport_input: false,
+            receives_input: true,
+            show_hud: false,
+            lock_cursor_while_dragging: false,
+            edge_scroll_enabled: false,
+            edge_scroll_margin_px: 20.0,
+            edge_scroll_speed: 0.5,
+            follow_target: None,
+            follow_smooth_time: 0.0,
+            follow_velocity: Vec3::ZERO,
+            follow_position: None,
+        }
+    }
+}
+
+impl CameraSettings {
+    // Settings that belong to each camera rather than to the tuning shared between them.
+    const PER_CAMERA_FIELDS: &'static [&'static str] = &[
+        "orbit_target",
+        "orbit_distance",
+        "target_distance",
+        "mode",
+        "projection_mode",
+        "show_axes",
+        "show_hud",
+        "initial_yaw",
+        "initial_pitch",
+        "initial_roll",
+        "initial_target",
+        "initial_distance",
+        "viewport_input",
+        "scene_path",
+    ];
+
+    /// The names in `fields` that [`CameraSettings::apply_tuning`] won't take from a file.
+    fn per_camera_fields(fields: &[String]) -> impl Iterator<Item = &str> {
+        fields
+            .iter()
+            .map(String::as_str)
+            .filter(|name| Self::PER_CAMERA_FIELDS.contains(name))
+    }
+
+    /// Take the settings named in `fields` from `tuning`, keeping everything else along with this
+    /// camera's current pose, mode, toggles, the per-camera setup it was spawned with (starting
+    /// view, viewport input, scene) and the state it carries between frames. Reloading
+    /// the settings file uses this, so the camera doesn't jump and settings the file leaves out
+    /// stay as they were.
+    ///
+    /// These are never taken from `tuning`, even when named, and loading a file that sets one
+    /// logs a warning: `orbit_target`, `orbit_distance`, `target_distance`, `mode`,
+    /// `projection_mode`, `show_axes`, `show_hud`, `initial_yaw`, `initial_pitch`, `initial_roll`,
+    /// `initial_target`, `initial_distance`, `viewport_input` and `scene_path`.
+    pub fn apply_tuning(&mut self, tuning: &CameraSettings, fields: &[String]) {
+        let state = self.clone();
+        for name in fields {
+            if Self::PER_CAMERA_FIELDS.contains(&name.as_str()) {
+                continue;
+            }
+            if let (Some(field), Some(value)) = (self.field_mut(name), tuning.field(name)) {
+                field.apply(value);
+            }
+        }
+        self.distance_velocity = state.distance_velocity;
+        self.target_transition = state.target_transition;
+        self.projection_transition = state.projection_transition;
+        self.zoom_anchor = state.zoom_anchor;
+        self.yaw_velocity = state.yaw_velocity;
+        self.pitch_velocity = state.pitch_velocity;
+        self.requested_angles = state.requested_angles;
+        self.pending_rotation = state.pending_rotation;
+        self.rotation_velocity = state.rotation_velocity;
+        self.pending_pan = state.pending_pan;
+        self.pan_velocity = state.pan_velocity;
+        self.queued_rotation = state.queued_rotation;
+        self.drag_zoom_toggled = state.drag_zoom_toggled;
+        self.receives_input = state.receives_input;
+        self.key_ramp = state.key_ramp;
+        self.idle_time = state.idle_time;
+        self.shake = state.shake;
+        self.sway_phases = state.sway_phases;
+        self.sway_weight = state.sway_weight;
+        self.unswayed_rotation = state.unswayed_rotation;
+        self.focus_gizmo_age = state.focus_gizmo_age;
+        self.hovered = state.hovered;
+        self.ortho_zoom_distance = state.ortho_zoom_distance;
+        self.collision_distance = state.collision_distance;
+        self.collision_velocity = state.collision_velocity;
+        self.smoothed_mouse_motion = state.smoothed_mouse_motion;
+        self.snap_yaw = state.snap_yaw;
+        self.bank_roll = state.bank_roll;
+        self.bank_velocity = state.bank_velocity;
+        self.follow_target = state.follow_target;
+        self.follow_position = state.follow_position;
+        self.follow_velocity = state.follow_velocity;
+    }
+
+    /// Drop any animation, momentum and input still being eased in, leaving the camera where it is.
+    fn stop_motion(&mut self) {
+        self.target_transition = None;
+        self.zoom_anchor = None;
+        self.yaw_velocity = 0.0;
+        self.pitch_velocity = 0.0;
+        self.distance_velocity = 0.0;
+        self.requested_angles = None;
+        self.queued_rotation = Vec3::ZERO;
+        self.pending_rotation = Vec3::ZERO;
+        self.rotation_velocity = Vec3::ZERO;
+        self.pending_pan = Vec3::ZERO;
+        self.pan_velocity = Vec3::ZERO;
+        self.key_ramp = Vec2::ZERO;
+        self.bank_roll = 0.0;
+        self.bank_velocity = 0.0;
+    }
+
+    /// Turn the camera to the given yaw, pitch and roll, in `euler_order`, on the next update,
+    /// replacing any orientation animation and momentum.
+    pub fn look_from_angles(&mut self, yaw: f32, pitch: f32, roll: f32) {
+        self.requested_angles = Some((yaw, pitch, roll));
+        self.bank_roll = 0.0;
+        self.bank_velocity = 0.0;
+        self.yaw_velocity = 0.0;
+        self.pitch_velocity = 0.0;
+        self.pending_rotation = Vec3::ZERO;
+        self.rotation_velocity = Vec3::ZERO;
+        if let Some(transition) = &mut self.target_transition {
+            transition.rotation = None;
+        }
+    }
+
+    /// Zoom to `distance` from the orbit target, within `distance_range`. The camera eases there
+    /// like it does for scroll input.
+    pub fn set_distance(&mut self, distance: f32) {
+        self.target_distance = distance.clamp(self.distance_range.start, self.distance_range.end);
+        self.zoom_anchor = None;
+    }
+
+    /// Start orbiting `target`, kept inside `target_bounds`, from `distance`, and make that the
+    /// view resetting returns to.
+    pub fn start_at(&mut self, target: Vec3, distance: f32) {
+        self.set_orbit_target(target);
+        self.orbit_distance = distance;
+        self.target_distance = distance;
+        self.initial_target = self.orbit_target;
+        self.initial_distance = self.orbit_distance;
+    }
+
+    /// Move the orbit target, keeping it inside `target_bounds` when they are set.
+    pub fn set_orbit_target(&mut self, target: Vec3) {
+        self.orbit_target = match self.target_bounds {
+            Some((min, max)) => target.clamp(min, max),
+            None => target,
+        };
+    }
+
+    /// Animate the orbit target to `desired_target`, following `easing`. Starting from the current
+    /// target means a new transition picks up smoothly from wherever an unfinished one had got to.
+    /// The target stops following `follow_target`, as it is now headed elsewhere.
+    pub fn transition_target_to(&mut self, desired_target: Vec3, easing: Easing) {
+        self.pending_pan = Vec3::ZERO;
+        self.pan_velocity = Vec3::ZERO;
+        self.follow_target = None;
+        self.follow_velocity = Vec3::ZERO;
+        self.target_transition = Some(TargetTransition {
+            start: self.orbit_target,
+            desired_target,
+            rotation: None,
+            easing,
+            elapsed: 0.0,
+        });
+    }
+
+    /// Animate the orbit target to `desired_target` while turning the camera from `rotation` to
+    /// `desired_rotation`.
+    pub fn transition_view_to(
+        &mut self,
+        desired_target: Vec3,
+        rotation: Quat,
+        desired_rotation: Quat,
+        easing: Easing,
+    ) {
+        self.transition_target_to(desired_target, easing);
+        if let Some(transition) = &mut self.target_transition {
+            transition.rotation = Some((rotation, desired_rotation));
+        }
+        self.pending_rotation = Vec3::ZERO;
+        self.rotation_velocity = Vec3::ZERO;
+    }
+
+    /// Animate the camera from its current `rotation` to the pose in `state`, dropping any
+    /// momentum or zoom anchor that would pull it elsewhere.
+    pub fn transition_to_state(&mut self, rotation: Quat, state: &CameraState, easing: Easing) {
+        self.target_distance = state.distance;
+        self.zoom_anchor = None;
+        self.yaw_velocity = 0.0;
+        self.pitch_velocity = 0.0;
+        let end_rotation = state.rotation(self.euler_order);
+        self.transition_view_to(state.target, rotation, end_rotation, easing);
+    }
+
+    /// Advance the target transition by `dt` seconds, moving the orbit target along it. Returns the
+    /// camera orientation for this frame if the transition is also turning the camera.
+    fn update_target_transition(&mut self, dt: f32) -> Option<Quat> {
+        let mut transition = self.target_transition?;
+
+        // Long enough to see where the camera went, but not to feel it move.
+        const REDUCED_TRANSITION_DURATION: f32 = 0.1;
+
+        transition.elapsed += dt;
+        let (duration, easing) = if self.reduce_motion {
+            let easing = match transition.easing {
+                Easing::Elastic => Easing::Smoothstep,
+                easing => easing,
+            };
+            (
+                self.transition_duration.min(REDUCED_TRANSITION_DURATION),
+                easing,
+            )
+        } else {
+            (self.transition_duration, transition.easing)
+        };
+        let t = if duration > 0.0 {
+            (transition.elapsed / duration).min(1.0)
+        } else {
+            1.0
+        };
+        let eased = ease(easing, t);
+        let target = transition.start.lerp(transition.desired_target, eased);
+        self.set_orbit_target(target);
+        self.target_transition = (t < 1.0).then_some(transition);
+
+        // Land exactly on the desired orientation at the end, rather than wherever slerp rounds to.
+        transition.rotation.map(|(start, end)| {
+            if t < 1.0 {
+                start.slerp(end, eased)
+            } else {
+                end
+            }
+        })
+    }
+
+    /// Pitch and yaw speed, in radians per pixel of mouse motion, at the current orbit distance.
+    ///
+    /// With `sensitivity_scales_with_distance` set, each speed is multiplied by
+    /// `(orbit_distance / sensitivity_reference_distance).powf(exponent)`, using that axis's
+    /// distance exponent, so rotation slows down as the camera closes in. Otherwise they are
+    /// `pitch_speed` and `yaw_speed` as they are.
+    pub fn rotation_speeds(&self) -> (f32, f32) {
+        if !self.sensitivity_scales_with_distance || self.sensitivity_reference_distance <= 0.0 {
+            return (self.pitch_speed, self.yaw_speed);
+        }
+        let ratio = self.orbit_distance / self.sensitivity_reference_distance;
+        (
+            self.pitch_speed * ratio.powf(self.pitch_distance_exponent),
+            self.yaw_speed * ratio.powf(self.yaw_distance_exponent),
+        )
+    }
+
+    /// How scrolling zooms a camera with `projection`, which is `zoom_mode` unless it can't work.
+    ///
+    /// An orthographic view has no field of view to narrow, and the same view from any distance.
+    /// With `ortho_zoom_scales_view` set, it zooms by dollying, and the view is scaled to match
+    /// the distance. A perspective view, or a camera without a projection, uses `zoom_mode`.
+    pub fn effective_zoom_mode(&self, projection: Option<&Projection>) -> ZoomMode {
+        match projection {
+            Some(Projection::Orthographic(_)) if self.ortho_zoom_scales_view => ZoomMode::Dolly,
+            _ => self.zoom_mode,
+        }
+    }
+
+    /// Multiplier for `pitch_speed` and `yaw_speed` with `constant_screen_speed` set, or 1 without.
+    ///
+    /// Turning by an angle moves the scene around the target by that angle times its radius, and
+    /// on screen that shows up divided by the world size of a pixel at the target. Scaling the
+    /// speed by the height of the view at the target keeps the two in proportion at any zoom. The
+    /// scale is 1 for a default perspective view from `sensitivity_reference_distance`.
+    ///
+    /// A perspective view is `2 * distance * tan(fov / 2)` tall at the target, so the speed grows
+    /// with distance and narrower fields of view slow it. An orthographic view is its `area` tall
+    /// at any distance, so moving the camera in or out doesn't change the speed: only zooming the
+    /// area does.
+    pub fn screen_speed_scale(&self, projection: &Projection) -> f32 {
+        if !self.constant_screen_speed || self.sensitivity_reference_distance <= 0.0 {
+            return 1.0;
+        }
+        // The pixel size over a single-pixel viewport is the size of the whole view.
+        let view_height = world_per_pixel(projection, self.orbit_distance, Vec2::ONE).y;
+        let reference = Projection::from(PerspectiveProjection::default());
+        let reference_height =
+            world_per_pixel(&reference, self.sensitivity_reference_distance, Vec2::ONE).y;
+        view_height / reference_height
+    }
+
+    /// Pitch and yaw speed for mouse and touch motion: [`Self::screen_speed_scale`] times the base
+    /// speeds with `constant_screen_speed` set, or [`Self::rotation_speeds`] otherwise or without a
+    /// projection.
+    pub fn pointer_rotation_speeds(&self, projection: Option<&Projection>) -> (f32, f32) {
+        match projection {
+            Some(projection) if self.constant_screen_speed => {
+                let scale = self.screen_speed_scale(projection);
+                (self.pitch_speed * scale, self.yaw_speed * scale)
+            }
+            _ => self.rotation_speeds(),
+        }
+    }
+
+    /// This frame's vertical scroll as a number of zoom steps, positive for zooming in. Wheels
+    /// scroll by lines and trackpads by pixels, so each is scaled to make them feel alike.
+    pub fn scroll_steps(&self, scroll: &AccumulatedMouseScroll) -> f32 {
+        let scale = match scroll.unit {
+            MouseScrollUnit::Line => self.scroll_line_scale,
+            MouseScrollUnit::Pixel => self.scroll_pixel_scale,
+        };
+        let steps = scroll.delta.y * scale;
+        if self.invert_scroll {
+            -steps
+        } else {
+            steps
+        }
+    }
+
+    /// Whether to ignore mouse input this frame, because the camera isn't taking input or the
+    /// cursor is over UI that wants it.
+    fn pointer_blocked(&self, ui_blocking: bool) -> bool {
+        !self.receives_input || (self.block_input_over_ui && ui_blocking)
+    }
+
+    /// The `near` and `far` planes to use, with near kept in front of the camera and far kept
+    /// beyond near.
+    pub fn clip_planes(&self) -> (f32, f32) {
+        // Closest the near plane may be to the camera, and to the far plane.
+        const MIN_NEAR: f32 = 0.001;
+        const MIN_DEPTH: f32 = 0.01;
+
+        let near = self.near.max(MIN_NEAR);
+        (near, self.far.max(near + MIN_DEPTH))
+    }
+
+    /// Move the far plane out just far enough to see a scene of the given radius around the target
+    /// from anywhere in `distance_range`, with some room to pan.
+    fn fit_far_plane(&mut self, radius: f32, distance: f32) {
+        const MARGIN: f32 = 2.0;
+        self.far = (self.distance_range.end.max(distance) + radius) * MARGIN;
+    }
+
+    /// Speed multiplier for this frame, reduced while the precision modifier is held.
+    fn precision_scale(&self, keys: &ButtonInput<KeyCode>, bindings: &KeyBindings) -> f32 {
+        if keys.pressed(bindings.precision) {
+            self.precision_factor
+        } else {
+            1.0
+        }
+    }
+}
+
+/// Move `current` toward `target` with a critically damped spring, like Unity's `SmoothDamp`.
+///
+/// `velocity` carries the rate of change from one call to the next, and `smooth_time` is roughly
+/// how long getting to `target` takes, in seconds. Stepping the spring's exact solution keeps the
+/// motion the same at any frame rate, and the result never passes `target`. A `smooth_time` of 0
+/// jumps straight there.
+pub fn smooth_damp(
+    current: f32,
+    target: f32,
+    velocity: &mut f32,
+    smooth_time: f32,
+    dt: f32,
+) -> f32 {
+    if smooth_time <= 0.0 {
+        *velocity = 0.0;
+        return target;
+    }
+    let (position, next_velocity) =
+        critically_damped_step(current, *velocity, target, 2.0 / smooth_time, dt);
+    // A velocity carried over from a target further away could otherwise overshoot this one.
+    if (target > current) == (position > target) {
+        *velocity = 0.0;
+        return target;
+    }
+    *velocity = next_velocity;
+    position
+}
+
+/// [`smooth_damp`] on each axis, so the result never passes `target` along any of them.
+fn smooth_damp_vec3(
+    current: Vec3,
+    target: Vec3,
+    velocity: &mut Vec3,
+    smooth_time: f32,
+    dt: f32,
+) -> Vec3 {
+    let mut result = target;
+    for axis in 0..3 {
+        result[axis] = smooth_damp(
+            current[axis],
+            target[axis],
+            &mut velocity[axis],
+            smooth_time,
+            dt,
+        );
+    }
+    result
+}
+
+/// Ease the rest of `offset` in with [`smooth_damp`] on each axis, returning the part to apply this
+/// frame. Once what's left is within `epsilon`, all of it is applied and the spring stops.
+fn smooth_damp_offset(
+    offset: &mut Vec3,
+    velocity: &mut Vec3,
+    smooth_time: f32,
+    dt: f32,
+    epsilon: f32,
+) -> Vec3 {
+    let remaining = smooth_damp_vec3(*offset, Vec3::ZERO, velocity, smooth_time, dt);
+    if remaining.abs().max_element() < epsilon {
+        *velocity = Vec3::ZERO;
+        return std::mem::take(offset);
+    }
+    let step = *offset - remaining;
+    *offset = remaining;
+    step
+}
+
+/// Whether the cursor is over, or pressing, a UI node that takes input, such as a button or slider.
+fn ui_wants_input(interactions: &Query<&Interaction>) -> bool {
+    interactions
+        .iter()
+        .any(|interaction| *interaction != Interaction::None)
+}
+
+/// Ray from `camera` through the window position `cursor`, or `None` when the cursor isn't over
+/// the camera's viewport.
+///
+/// `Camera::viewport_to_world` takes positions relative to the viewport's own corner, so for a
+/// viewport that doesn't start at the window's, such as the right half of a split screen, that
+/// corner is taken off the cursor first.
+fn cursor_ray(camera: &Camera, camera_transform: &GlobalTransform, cursor: Vec2) -> Option<Ray3d> {
+    let viewport = camera.logical_viewport_rect()?;
+    if !viewport.contains(cursor) {
+        return None;
+    }
+    camera
+        .viewport_to_world(camera_transform, cursor - viewport.min)
+        .ok()
+}
+
+/// Ignore stick deflections shorter than `deadzone`, rescaling the rest so output still starts at
+/// zero and reaches full deflection. Working on the length rather than per axis keeps the dead
+/// region round.
+fn radial_deadzone(stick: Vec2, deadzone: f32) -> Vec2 {
+    let length = stick.length();
+    if length <= deadzone {
+        return Vec2::ZERO;
+    }
+    let scaled = ((length - deadzone) / (1.0 - deadzone)).min(1.0);
+    stick * (scaled / length)
+}
+
+/// Raise each axis of a stick deflection to the power `curve`, keeping its sign, so small
+/// deflections turn slowly while full deflection still gives full speed.
+fn response_curve(stick: Vec2, curve: f32) -> Vec2 {
+    stick.signum() * stick.abs().powf(curve)
+}
+
+/// Distance from which a sphere of the given radius fills the view without being cropped.
+///
+/// An orthographic view's framing doesn't depend on distance, so it is treated like the default
+/// perspective field of view. That keeps the subject framed when switching projection later.
+pub fn fit_distance_for_bounds(radius: f32, projection: &Projection) -> f32 {
+    let (fov, aspect_ratio) = match projection {
+        Projection::Perspective(perspective) => (perspective.fov, perspective.aspect_ratio),
+        _ => {
+            let perspective = PerspectiveProjection::default();
+            (perspective.fov, perspective.aspect_ratio)
+        }
+    };
+
+    // `fov` is vertical; in a portrait window the horizontal field of view is the narrower one.
+    let horizontal_fov = 2.0 * ((fov / 2.0).tan() * aspect_ratio).atan();
+    radius / (fov.min(horizontal_fov) / 2.0).sin()
+}
+
+/// World-space size of one logical pixel at the orbit target, across and up the view.
+///
+/// A perspective view at `distance` is `2 * distance * tan(fov / 2)` tall, spread over the
+/// viewport's height, and pixels are square so the width matches. An orthographic view shows its
+/// `area` over the whole viewport whatever the distance. For `ScalingMode::Fixed { width, height }`
+/// that is `width * scale` by `height * scale`, so the two sizes differ when the window's shape
+/// doesn't match.
+pub fn world_per_pixel(projection: &Projection, distance: f32, viewport_size: Vec2) -> Vec2 {
+    if viewport_size.x <= 0.0 || viewport_size.y <= 0.0 {
+        return Vec2::ZERO;
+    }
+    match projection {
+        Projection::Perspective(perspective) => {
+            Vec2::splat(2.0 * distance * (perspective.fov / 2.0).tan() / viewport_size.y)
+        }
+        Projection::Orthographic(orthographic) => orthographic.area.size() / viewport_size,
+    }
+}
+
+/// Center of `entity` and the distance that frames it, with a little room around it.
+///
+/// The entity's `Aabb` is moved into world space and fitted like [`fit_distance_for_bounds`] does.
+/// An entity without an `Aabb`, such as one whose mesh hasn't loaded yet, is framed as a unit
+/// sphere around its position. Returns `None` if the entity has no `GlobalTransform`.
+pub fn fit_distance_for_entity(
+    entity: Entity,
+    transforms: &Query<&GlobalTransform>,
+    aabbs: &Query<&Aabb>,
+    projection: &Projection,
+) -> Option<(Vec3, f32)> {
+    // Keeps the entity clear of the edges of the view.
+    const PADDING: f32 = 1.1;
+
+    let transform = transforms.get(entity).ok()?;
+    let (center, radius) = match aabbs.get(entity) {
+        Ok(aabb) => (
+            transform.transform_point(aabb.center.into()),
+            transform.radius_vec3a(aabb.half_extents),
+        ),
+        Err(_) => (transform.translation(), 1.0),
+    };
+    Some((
+        center,
+        fit_distance_for_bounds(radius * PADDING, projection),
+    ))
+}
+
+/// Bounding sphere, as its center and radius, around all the given world-space boxes.
+pub fn bounding_sphere(boxes: impl IntoIterator<Item = (Vec3, Vec3)>) -> Option<(Vec3, f32)> {
+    let (min, max) = boxes
+        .into_iter()
+        .reduce(|(min_a, max_a), (min_b, max_b)| (min_a.min(min_b), max_a.max(max_b)))?;
+    let center = (min + max) / 2.0;
+    Some((center, (max - center).length()))
+}
+
+/// World-space box around a local-space `aabb` placed with `transform`.
+pub fn world_aabb(aabb: &Aabb, transform: &GlobalTransform) -> (Vec3, Vec3) {
+    let center = transform.transform_point(aabb.center.into());
+    // Each world axis extends by the projection of the local half extents onto it.
+    let matrix = transform.affine().matrix3;
+    let half_extents = Vec3::from(
+        Mat3A::from_cols(
+            matrix.x_axis.abs(),
+            matrix.y_axis.abs(),
+            matrix.z_axis.abs(),
+        ) * aabb.half_extents,
+    );
+    (center - half_extents, center + half_extents)
+}
+
+/// Transform of a camera orbiting `target` at `distance`, turned by the given yaw, pitch and roll
+/// applied in `order`, normally the camera's `euler_order`.
+///
+/// The orbit controls place the camera with this same function, so code driving the camera
+/// directly lands exactly where interactive input would.
+pub fn compute_orbit_transform(
+    target: Vec3,
+    yaw: f32,
+    pitch: f32,
+    roll: f32,
+    distance: f32,
+    order: EulerRot,
+) -> Transform {
+    let rotation = Quat::from_euler(order, yaw, pitch, roll);
+    orbit_transform(target, rotation, distance)
+}
+
+/// Rotation and zoom to apply to an orbit camera in one update.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct OrbitInput {
+    pub delta_yaw: f32,
+    pub delta_pitch: f32,
+    pub delta_roll: f32,
+    /// Change in distance from the orbit target
+    pub delta_distance: f32,
+}
+
+/// Where an orbit camera at `current` ends up after applying `input`.
+///
+/// The input is composed onto the orientation of `current`, or the one given by
+/// `settings.requested_angles` when set: yaw turns about the world up axis, pitch about the
+/// camera's right and roll about its forward direction. Working on the quaternion directly avoids
+/// the drift of converting to Euler angles and back every frame. Pitch stays within
+/// `settings.pitch_range`, and the camera is placed relative to `settings.orbit_target`.
+pub fn next_orbit_transform(
+    current: &Transform,
+    settings: &CameraSettings,
+    input: OrbitInput,
+) -> Transform {
+    let rotation = match settings.requested_angles {
+        Some((yaw, pitch, roll)) => Quat::from_euler(settings.euler_order, yaw, pitch, roll),
+        None => current.rotation,
+    };
+    let rotation = turn(rotation, &input, settings);
+    let distance = settings.orbit_distance + input.delta_distance;
+
+    orbit_transform(settings.orbit_target, rotation, distance)
+}
+
+/// Compose the yaw, pitch and roll of `input` onto `rotation`, keeping pitch in `pitch_range`
+/// unless `allow_full_pitch` is set, and yaw in `yaw_range`.
+fn turn(rotation: Quat, input: &OrbitInput, settings: &CameraSettings) -> Quat {
+    let pitch_range = &settings.pitch_range;
+    let forward = rotation * Vec3::NEG_Z;
+
+    // Measure the pitch as the elevation of the view direction, and only pitch as far as the
+    // limits allow. When the camera is rolled, turning about its right axis raises the view by no
+    // more than the pitch angle, so the limits still hold.
+    let delta_pitch = if settings.allow_full_pitch {
+        input.delta_pitch
+    } else {
+        let pitch = forward.y.clamp(-1.0, 1.0).asin();
+        let delta_pitch = soften_pitch_delta(pitch, input.delta_pitch, settings);
+        (pitch + delta_pitch).clamp(pitch_range.start, pitch_range.end) - pitch
+    };
+
+    // Likewise, yaw is the heading of the view direction.
+    let delta_yaw = match &settings.yaw_range {
+        Some(yaw_range) => {
+            let yaw = f32::atan2(-forward.x, -forward.z);
+            clamp_yaw(yaw + input.delta_yaw, yaw_range) - yaw
+        }
+        None => input.delta_yaw,
+    };
+    // Pitching through a pole turns the camera upside down, where turning about world up would
+    // look backwards on screen. Turning about whichever way up the camera is keeps it consistent,
+    // with no sudden flip at the pole.
+    let delta_yaw = if settings.allow_full_pitch && (rotation * Vec3::Y).y < 0.0 {
+        -delta_yaw
+    } else {
+        delta_yaw
+    };
+
+    let rotation = Quat::from_rotation_y(delta_yaw)
+        * rotation
+        * Quat::from_rotation_x(delta_pitch)
+        * Quat::from_rotation_z(input.delta_roll);
+    rotation.normalize()
+}
+
+/// Slow down pitching toward an end of `pitch_range` once within `soft_pitch_margin` of it.
+///
+/// The delta is scaled by `1 - depth`, where `depth` runs from 0 at the inner edge of the margin
+/// to 1 at the limit, so the camera eases to a stop. Pitching back out is never slowed.
+fn soften_pitch_delta(pitch: f32, delta_pitch: f32, settings: &CameraSettings) -> f32 {
+    let margin = settings.soft_pitch_margin;
+    if margin <= 0.0 {
+        return delta_pitch;
+    }
+    let room = if delta_pitch > 0.0 {
+        settings.pitch_range.end - pitch
+    } else {
+        pitch - settings.pitch_range.start
+    };
+    let depth = 1.0 - room / margin;
+    delta_pitch * (1.0 - depth).clamp(0.0, 1.0)
+}
+
+/// Clamp `yaw` to the arc running with increasing angle from `range.start` to `range.end`,
+/// treating angles a full turn apart as the same.
+///
+/// An end below the start means the arc wraps past a full turn, so `5.0..1.0` covers the
+/// `TAU - 4.0` radians through 0, not the 4 between 1 and 5. Working relative to the middle of the
+/// arc handles ranges that cross ±π, like `3.0..3.5`, and makes a yaw just past either end stop at
+/// that end rather than snapping to the other one.
+fn clamp_yaw(yaw: f32, range: &Range<f32>) -> f32 {
+    let end = if range.end < range.start {
+        range.end + TAU
+    } else {
+        range.end
+    };
+    let middle = (range.start + end) / 2.0;
+    let half_width = (end - range.start) / 2.0;
+    let offset = (yaw - middle + PI).rem_euclid(TAU) - PI;
+    middle + offset.clamp(-half_width, half_width)
+}
+
+/// How far `rotation` is rolled about its view direction away from level, in radians. There is
+/// no meaningful roll when looking straight up or down, so that gives `None`.
+///
+/// With `allow_full_pitch`, a camera that has tumbled over a pole counts as level when it is
+/// exactly upside down, so levelling it doesn't turn it the right way up.
+fn roll_angle(rotation: Quat, allow_full_pitch: bool) -> Option<f32> {
+    let forward = rotation * Vec3::NEG_Z;
+    if forward.y.abs() > 0.999 {
+        return None;
+    }
+    let level_up = Transform::default().looking_to(forward, Vec3::Y).up();
+    let up = rotation * Vec3::Y;
+    let roll = f32::atan2(level_up.cross(up).dot(-forward), level_up.dot(up));
+    if allow_full_pitch {
+        Some((roll + FRAC_PI_2).rem_euclid(PI) - FRAC_PI_2)
+    } else {
+        Some(roll)
+    }
+}
+
+/// Lift the camera up to `min_height` if it is below it, turning it to keep looking at `target`.
+///
+/// When the target is on or below the floor too, the camera can end up level with or right above
+/// it. Looking straight down keeps the current up direction where possible, and a camera sitting
+/// exactly on the target keeps its orientation.
+fn raise_to_floor(camera: &mut Transform, target: Vec3, min_height: f32) {
+    if camera.translation.y >= min_height {
+        return;
+    }
+    camera.translation.y = min_height;
+    if camera.translation.distance_squared(target) > f32::EPSILON {
+        let up = camera.up();
+        camera.look_at(target, up);
+    }
+}
+
+/// Transform of a camera with the given `rotation`, backed away from `target` to look at it.
+fn orbit_transform(target: Vec3, rotation: Quat, distance: f32) -> Transform {
+    let transform = Transform::from_rotation(rotation);
+    transform.with_translation(target - transform.forward() * distance)
+}
+
+/// Where a camera with `settings` starts. Spawning the camera at the transform its settings
+/// describe means the first orbit update has nothing to correct, so it doesn't snap.
+pub fn initial_camera_transform(settings: &CameraSettings) -> Transform {
+    compute_orbit_transform(
+        settings.orbit_target,
+        settings.initial_yaw,
+        settings.initial_pitch,
+        settings.initial_roll,
+        settings.orbit_distance,
+        settings.euler_order,
+    )
+}
+
+/// Camera tuning loaded from a RON file holding a [`CameraSettings`].
+#[derive(Debug, Clone, Asset, TypePath)]
+pub struct CameraSettingsAsset {
+    pub settings: CameraSettings,
+    // The settings the file sets, so only those are applied.
+    pub fields: Vec<String>,
+}
+
+/// Why a camera settings file couldn't be loaded.
+#[derive(Debug)]
+pub enum CameraSettingsLoaderError {
+    Io(std::io::Error),
+    Parse(ron::error::SpannedError),
+}
+
+impl fmt::Display for CameraSettingsLoaderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "could not read the file: {error}"),
+            Self::Parse(error) => write!(f, "could not parse the file: {error}"),
+        }
+    }
+}
+
+impl Error for CameraSettingsLoaderError {}
+
+impl From<std::io::Error> for CameraSettingsLoaderError {
+    fn from(error: std::io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<ron::error::SpannedError> for CameraSettingsLoaderError {
+    fn from(error: ron::error::SpannedError) -> Self {
+        Self::Parse(error)
+    }
+}
+
+#[derive(Default)]
+struct CameraSettingsLoader;
+
+impl AssetLoader for CameraSettingsLoader {
+    type Asset = CameraSettingsAsset;
+    type Settings = ();
+    type Error = CameraSettingsLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let fields = match ron::de::from_bytes(&bytes)? {
+            ron::Value::Map(map) => map
+                .keys()
+                .filter_map(|key| match key {
+                    ron::Value::String(name) => Some(name.clone()),
+                    _ => None,
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+        Ok(CameraSettingsAsset {
+            settings: ron::de::from_bytes(&bytes)?,
+            fields,
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["ron"]
+    }
+}
+
+/// Keeps the camera settings asset loaded, so changes to the file are picked up.
+#[derive(Resource)]
+struct CameraSettingsFile(Handle<CameraSettingsAsset>);
+
+/// Random source for every random camera effect, such as shake. It starts from `rng_seed` of the
+/// first orbit camera in render order, and starts over whenever that seed changes, so the same
+/// seed plays the same effects out identically on every run.
+#[derive(Debug, Clone, Resource)]
+pub struct CameraRng {
+    rng: StdRng,
+    seed: u64,
+}
+
+impl CameraRng {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: StdRng::seed_from_u64(seed),
+            seed,
+        }
+    }
+
+    /// The seed this sequence started from.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+}
+
+impl Default for CameraRng {
+    fn default() -> Self {
+        Self::new(CameraSettings::default().rng_seed)
+    }
+}
+
+impl RngCore for CameraRng {
+    fn next_u32(&mut self) -> u32 {
+        self.rng.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.rng.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.rng.fill_bytes(dest);
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.rng.try_fill_bytes(dest)
+    }
+}
+
+/// Adds the orbit camera controls, driving every camera marked with [`OrbitCamera`].
+pub struct CameraControlPlugin;
+
+/// The `Update` systems that read camera input and move the cameras: orbiting, zooming, panning,
+/// transitions and the effects layered on top. The gizmos and HUD run after it.
+///
+/// Order a system `.before(CameraControlSet)` to have the cameras react to what it does this frame,
+/// such as moving a followed entity, or `.after(CameraControlSet)` to see where they ended up.
+/// Following reads the followed entity's local `Transform`, plus its parent's `GlobalTransform`,
+/// so moving it before the set is enough, without waiting for transform propagation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, SystemSet)]
+pub struct CameraControlSet;
+
+impl Plugin for CameraControlPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Viewpoints>()
+            .init_resource::<CameraRng>()
+            .init_resource::<KeyBindings>()
+            .init_resource::<FocusCycle>()
+            .init_resource::<CameraPath>()
+            // Registered so inspectors can tweak the settings at runtime.
+            .register_type::<CameraSettings>()
+            .init_asset::<CameraSettingsAsset>()
+            .init_asset_loader::<CameraSettingsLoader>()
+            .add_event::<CameraMoved>()
+            .add_event::<CameraShake>()
+            .add_event::<FrameAll>()
+            .add_observer(focus_camera)
+            .add_systems(
+                Startup,
+                (load_viewpoints, load_path, load_camera_settings, spawn_hud),
+            )
+            // The scene is spawned during `Startup`, so it can only be measured afterwards.
+            .add_systems(PostStartup, (fit_scene, apply_clip_planes).chain())
+            .add_systems(
+                Update,
+                (
+                    // Clean up the input and last frame's effects, then handle toggles.
+                    (
+                        ignore_unfocused_motion,
+                        (next_camera, route_input).chain(),
+                        (apply_camera_settings, seed_camera_rng).chain(),
+                        remove_shake,
+                        (
+                            toggle_axes,
+                            toggle_hud,
+                            toggle_mode,
+                            path_controls,
+                            nudge_clip_planes,
+                            toggle_drag_zoom,
+                        ),
+                        toggle_projection,
+                        apply_framing_policy,
+                        apply_clip_planes,
+                    )
+                        .chain(),
+                    // Jumps to other views.
+                    (
+                        reset_view,
+                        viewpoints,
+                        preset_view,
+                        save_viewpoints,
+                        save_path,
+                        focus_on_click,
+                        box_select,
+                        frame_all,
+                        focus_cycle,
+                        frame_focused,
+                    )
+                        .chain(),
+                    (
+                        zoom,
+                        zoom_to_cursor,
+                        zoom_fov,
+                        pan,
+                        touch_orbit,
+                        edge_scroll,
+                    )
+                        .chain(),
+                    // Place the camera, then layer effects on top.
+                    (
+                        free_look,
+                        orbit.run_if(orbit_needed),
+                        scale_ortho_view,
+                        play_path,
+                        record_path,
+                        avoid_collisions,
+                        follow_ground,
+                        morph_projection,
+                        keep_target_onscreen,
+                        shake,
+                        sway,
+                    )
+                        .chain(),
+                )
+                    .chain()
+                    .in_set(CameraControlSet),
+            )
+            .add_systems(
+                Update,
+                (
+                    (draw_axes, draw_focus_gizmo, highlight_hover, camera_hud),
+                    // Last, so nothing shows the HUD again before the capture.
+                    (lock_cursor, screenshot),
+                )
+                    .chain()
+                    .after(CameraControlSet),
+            );
+    }
+}
+
+/// Point the camera at the middle of the scene, from just far enough away to see all of it, unless
+/// its `fit_on_startup` is turned off. Resetting the view comes back here.
+///
+/// This runs before meshes get their `Aabb` components, so the bounds are computed from the mesh
+/// assets directly.
+fn fit_scene(
+    mut cameras: Query<(&mut CameraSettings, &Projection), With<OrbitCamera>>,
+    meshes: Res<Assets<Mesh>>,
+    scene: Query<(&Mesh3d, &Transform)>,
+) {
+    let boxes = scene.iter().filter_map(|(mesh, transform)| {
+        let aabb = meshes.get(mesh)?.compute_aabb()?;
+        Some(world_aabb(&aabb, &GlobalTransform::from(*transform)))
+    });
+    let Some((center, radius)) = bounding_sphere(boxes) else {
+        return;
+    };
+
+    for (mut camera_settings, projection) in &mut cameras {
+        if !camera_settings.fit_on_startup {
+            continue;
+        }
+        let distance = fit_distance_for_bounds(radius, projection);
+        camera_settings.start_at(center, distance);
+        camera_settings.fit_far_plane(radius, distance);
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn zoom(
+    mut cameras: Query<(&mut CameraSettings, Option<&Projection>), With<OrbitCamera>>,
+    mouse_scroll: Res<AccumulatedMouseScroll>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    mouse_motion: Res<AccumulatedMouseMotion>,
+    keys: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    gamepads: Query<&Gamepad>,
+    time: Res<Time>,
+    interactions: Query<&Interaction>,
+) {
+    // The right trigger zooms in and the left one zooms out.
+    let trigger_zoom: f32 = gamepads
+        .iter()
+        .map(|gamepad| {
+            gamepad.get(GamepadButton::RightTrigger2).unwrap_or(0.0)
+                - gamepad.get(GamepadButton::LeftTrigger2).unwrap_or(0.0)
+        })
+        .sum();
+    let ui_blocking = ui_wants_input(&interactions);
+    let zoom_dragging = bindings
+        .zoom
+        .is_some_and(|zoom| bindings.drag_pressed(&zoom, &mouse_buttons, &keys));
+    let orbit_dragging = bindings.drag_pressed(&bindings.orbit, &mouse_buttons, &keys);
+
+    for (mut camera_settings, projection) in &mut cameras {
+        if camera_settings.effective_zoom_mode(projection) != ZoomMode::Dolly {
+            continue;
+        }
+        let drag_zooming = zoom_dragging || (camera_settings.drag_zoom_toggled && orbit_dragging);
+
+        // Like mouse motion, scroll is already accumulated over the whole frame, so it must not be
+        // scaled by delta time. Scrolling up moves the camera toward the target.
+        let blocked = camera_settings.pointer_blocked(ui_blocking);
+        let scroll = if blocked {
+            0.0
+        } else {
+            camera_settings.scroll_steps(&mouse_scroll)
+        };
+        let precision = camera_settings.precision_scale(&keys, &bindings);
+        let zoom_speed = camera_settings.zoom_speed * precision;
+        let mut distance = camera_settings.target_distance - scroll * zoom_speed;
+
+        // Held triggers, unlike scroll, do need to be scaled by delta time.
+        if camera_settings.receives_input {
+            distance -=
+                trigger_zoom * camera_settings.gamepad_zoom_speed * precision * time.delta_secs();
+        }
+
+        // Dragging up zooms in. Zooming by a share of the distance feels the same at any range.
+        if drag_zooming && !blocked && mouse_motion.delta.y != 0.0 {
+            distance *= 1.0 + mouse_motion.delta.y * camera_settings.drag_zoom_speed * precision;
+            camera_settings.zoom_anchor = None;
+        }
+
+        // Clamp even when there was no scroll, so distances set from elsewhere respect the limits
+        // too.
+        camera_settings.target_distance = distance.clamp(
+            camera_settings.distance_range.start,
+            camera_settings.distance_range.end,
+        );
+    }
+}
+
+/// Anchor the zoom on whatever is under the cursor, so zooming moves toward that point rather than
+/// the orbit target.
+fn zoom_to_cursor(
+    mut cameras: Query<
+        (&mut CameraSettings, &Camera, &GlobalTransform, &Projection),
+        With<OrbitCamera>,
+    >,
+    mouse_scroll: Res<AccumulatedMouseScroll>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    mut ray_cast: MeshRayCast,
+    interactions: Query<&Interaction>,
+) {
+    if mouse_scroll.delta.y == 0.0 {
+        return;
+    }
+
+    let Some(cursor) = window.cursor_position() else {
+        return;
+    };
+
+    let ui_blocking = ui_wants_input(&interactions);
+    for (mut camera_settings, camera, camera_transform, projection) in &mut cameras {
+        if camera_settings.effective_zoom_mode(Some(projection)) != ZoomMode::Dolly
+            || camera_settings.pointer_blocked(ui_blocking)
+        {
+            continue;
+        }
+
+        // Only the camera the cursor is over zooms toward it, even when every camera takes input.
+        let Some(ray) = cursor_ray(camera, camera_transform, cursor) else {
+            continue;
+        };
+        let hit = ray_cast
+            .cast_ray(ray, &RayCastSettings::default())
+            .first()
+            .map(|(_, hit)| hit.point);
+
+        // When the ray misses all geometry, this falls back to zooming toward the orbit target.
+        camera_settings.zoom_anchor = hit;
+    }
+}
+
+/// Zoom by changing the field of view, which only has an effect on a perspective camera.
+fn zoom_fov(
+    mut cameras: Query<(&CameraSettings, &mut Projection), With<OrbitCamera>>,
+    mouse_scroll: Res<AccumulatedMouseScroll>,
+    keys: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    interactions: Query<&Interaction>,
+) {
+    if mouse_scroll.delta.y == 0.0 {
+        return;
+    }
+
+    let ui_blocking = ui_wants_input(&interactions);
+    for (camera_settings, mut projection) in &mut cameras {
+        if camera_settings.effective_zoom_mode(Some(&projection)) != ZoomMode::Fov
+            || camera_settings.pointer_blocked(ui_blocking)
+        {
+            continue;
+        }
+
+        if let Projection::Perspective(perspective) = &mut *projection {
+            let zoom_speed =
+                camera_settings.fov_zoom_speed * camera_settings.precision_scale(&keys, &bindings);
+            let fov = perspective.fov - camera_settings.scroll_steps(&mouse_scroll) * zoom_speed;
+            perspective.fov = fov.clamp(
+                camera_settings.fov_range.start,
+                camera_settings.fov_range.end,
+            );
+        }
+    }
+}
+
+fn pan(
+    mut cameras: Query<(&Transform, &mut CameraSettings, &Camera, &Projection), With<OrbitCamera>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    mouse_motion: Res<AccumulatedMouseMotion>,
+    keys: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    time: Res<Time>,
+    interactions: Query<&Interaction>,
+) {
+    // Pan left to ease in that's smaller than this is applied at once.
+    const SETTLED_EPSILON: f32 = 1e-4;
+
+    // Screen-space pan direction, with x to the right and y down like mouse motion. The arrows move
+    // the view, so the target moves the opposite way to a mouse drag.
+    let mut key_direction = Vec2::ZERO;
+    if keys.pressed(bindings.pan_left) {
+        key_direction.x -= 1.0;
+    }
+    if keys.pressed(bindings.pan_right) {
+        key_direction.x += 1.0;
+    }
+    if keys.pressed(bindings.pan_up) {
+        key_direction.y -= 1.0;
+    }
+    if keys.pressed(bindings.pan_down) {
+        key_direction.y += 1.0;
+    }
+
+    for (camera, mut camera_settings, render_camera, projection) in &mut cameras {
+        let mut delta = Vec2::ZERO;
+
+        // Scaling each pixel by its size at the target moves the scene the same distance on screen
+        // as the cursor, however far away the camera is and whichever projection it uses. Like
+        // orbiting, mouse motion is not scaled by delta time.
+        let blocked = camera_settings.pointer_blocked(ui_wants_input(&interactions));
+        if !blocked && bindings.drag_pressed(&bindings.pan, &mouse_buttons, &keys) {
+            let viewport_size = render_camera.logical_viewport_size().unwrap_or(Vec2::ZERO);
+            let pixel = world_per_pixel(projection, camera_settings.orbit_distance, viewport_size);
+            delta += mouse_motion.delta * pixel * camera_settings.pan_speed;
+        }
+
+        // Held keys, on the other hand, must be scaled by delta time.
+        let key_direction = if camera_settings.receives_input {
+            key_direction
+        } else {
+            Vec2::ZERO
+        };
+        delta -= key_direction
+            * camera_settings.key_pan_speed
+            * camera_settings.orbit_distance
+            * time.delta_secs();
+
+        if delta != Vec2::ZERO {
+            // Panning takes over from any animation or followed entity that was moving the target.
+            camera_settings.target_transition = None;
+            camera_settings.follow_target = None;
+            camera_settings.follow_velocity = Vec3::ZERO;
+            camera_settings.focus_gizmo_age = 0.0;
+
+            // Move the target opposite to the drag, so the scene follows the cursor.
+            camera_settings.pending_pan += camera.up() * delta.y - camera.right() * delta.x;
+        }
+        if camera_settings.pending_pan == Vec3::ZERO {
+            continue;
+        }
+
+        // The pan is eased in here rather than in `orbit`, as it also moves the first-person
+        // camera.
+        let smooth_time = camera_settings.pan_smoothing;
+        let CameraSettings {
+            pending_pan,
+            pan_velocity,
+            ..
+        } = &mut *camera_settings;
+        let step = smooth_damp_offset(
+            pending_pan,
+            pan_velocity,
+            smooth_time,
+            time.delta_secs(),
+            SETTLED_EPSILON,
+        );
+        let target = camera_settings.orbit_target + step;
+        camera_settings.set_orbit_target(target);
+    }
+}
+
+/// How much further back than the orbit distance the camera gets at the flat end of a projection
+/// morph, where the nearly flat perspective gives way to the orthographic projection. It's far
+/// enough back that the switch can't be seen, without piling up so much distance that depth
+/// precision suffers.
+const MORPH_STRETCH: f32 = 40.0;
+
+/// The orthographic projection showing as much at the orbit target as `perspective` does, shaped
+/// to the window by `framing_policy`.
+fn orthographic_matching(
+    camera_settings: &CameraSettings,
+    perspective: &PerspectiveProjection,
+) -> Projection {
+    let height = 2.0 * camera_settings.orbit_distance * (perspective.fov / 2.0).tan();
+    let width = height * perspective.aspect_ratio;
+    Projection::from(OrthographicProjection {
+        scaling_mode: camera_settings.framing_policy.scaling_mode(width, height),
+        ..OrthographicProjection::default_3d()
+    })
+}
+
+/// Pressing P, by default, switches between orthographic and perspective projection. The new
+/// projection is chosen so the view at the orbit target is about the same height, keeping its
+/// framing.
+fn toggle_projection(
+    mut cameras: Query<(&mut CameraSettings, &mut Projection), With<OrbitCamera>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+) {
+    let toggled = bindings
+        .toggle_projection
+        .just_pressed(&mouse_buttons, &keys);
+    for (mut camera_settings, mut projection) in &mut cameras {
+        if toggled {
+            camera_settings.projection_mode = match camera_settings.projection_mode {
+                ProjectionMode::Orthographic => ProjectionMode::Perspective,
+                ProjectionMode::Perspective => ProjectionMode::Orthographic,
+            };
+        }
+
+        // A morph under way finishes first. If the mode was toggled back meanwhile, the check
+        // below then starts the morph back.
+        if camera_settings.projection_transition.is_some() {
+            continue;
+        }
+        let morph = camera_settings.projection_transition_time > 0.0
+            && !camera_settings.reduce_motion
+            && camera_settings.mode == CameraMode::Orbit;
+
+        // A perspective camera sees a view 2 * distance * tan(fov / 2) high at the target, while an
+        // orthographic one sees the same height everywhere. Matching the two at the orbit distance
+        // keeps the subject roughly the same size across the switch.
+        let distance = camera_settings.orbit_distance;
+        match (camera_settings.projection_mode, &*projection) {
+            (ProjectionMode::Perspective, Projection::Orthographic(orthographic)) => {
+                let height = orthographic.area.height();
+                let mut fov = (2.0 * (height / (2.0 * distance)).atan()).clamp(
+                    camera_settings.fov_range.start,
+                    camera_settings.fov_range.end,
+                );
+                // Start from a nearly flat perspective, far back, and let the morph bring it in.
+                if morph {
+                    let target_tan = (fov / 2.0).tan();
+                    let start_tan = target_tan / MORPH_STRETCH;
+                    camera_settings.projection_transition = Some(ProjectionTransition {
+                        start_tan,
+                        end_tan: target_tan,
+                        target_tan,
+                        elapsed: 0.0,
+                    });
+                    fov = 2.0 * start_tan.atan();
+                }
+                *projection = Projection::from(PerspectiveProjection { fov, ..default() });
+            }
+            (ProjectionMode::Orthographic, Projection::Perspective(perspective)) => {
+                // Stay in perspective while the morph flattens it, switching once it's done.
+                if morph {
+                    let target_tan = (perspective.fov / 2.0).tan();
+                    camera_settings.projection_transition = Some(ProjectionTransition {
+                        start_tan: target_tan,
+                        end_tan: target_tan / MORPH_STRETCH,
+                        target_tan,
+                        elapsed: 0.0,
+                    });
+                    continue;
+                }
+                *projection = orthographic_matching(&camera_settings, perspective);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Advance each projection morph that `toggle_projection` started, as a dolly zoom.
+///
+/// An orthographic projection is the limit of a perspective one whose field of view narrows to
+/// nothing as the camera backs off to infinity. The morph follows that path part of the way, with
+/// a perspective projection throughout. It blends `tan(fov / 2)`, which sets how strongly depth
+/// shrinks things, evenly between the full perspective and `MORPH_STRETCH` times flatter. Each
+/// frame the camera is moved back along its view by the same factor the tangent shrank by. A view
+/// `2 * distance * tan(fov / 2)` tall then stays the same height at the target, so the subject
+/// keeps its size while the rest of the scene flattens or deepens around it. The clip planes move
+/// back with the camera, keeping the same slice of the scene. At the flat end, the projection
+/// turns orthographic, with the same height.
+///
+/// Blending the projection matrices directly would need a custom `Projection`, which this version
+/// of Bevy doesn't have.
+fn morph_projection(
+    mut cameras: Query<(&mut Transform, &mut CameraSettings, &mut Projection), With<OrbitCamera>>,
+    time: Res<Time>,
+) {
+    for (mut camera, mut camera_settings, mut projection) in &mut cameras {
+        let Some(mut transition) = camera_settings.projection_transition else {
+            continue;
+        };
+        let Projection::Perspective(perspective) = &mut *projection else {
+            camera_settings.projection_transition = None;
+            continue;
+        };
+
+        transition.elapsed += time.delta_secs();
+        let duration = camera_settings.projection_transition_time;
+        let t = if duration > 0.0 {
+            (transition.elapsed / duration).min(1.0)
+        } else {
+            1.0
+        };
+        let tan = transition.start_tan
+            + (transition.end_tan - transition.start_tan) * ease(Easing::Smoothstep, t);
+
+        // Collision avoidance may have drawn the camera in, so back off from where it is now.
+        let distance = camera_settings
+            .collision_distance
+            .unwrap_or(camera_settings.orbit_distance);
+        let extra = distance * (transition.target_tan / tan - 1.0);
+        camera.translation = camera_settings.orbit_target + camera.back() * (distance + extra);
+        let (near, far) = camera_settings.clip_planes();
+        perspective.fov = 2.0 * tan.atan();
+        perspective.near = near + extra;
+        perspective.far = far + extra;
+
+        if t < 1.0 {
+            camera_settings.projection_transition = Some(transition);
+            continue;
+        }
+        camera_settings.projection_transition = None;
+        if camera_settings.projection_mode == ProjectionMode::Orthographic {
+            let flat = PerspectiveProjection {
+                fov: 2.0 * transition.target_tan.atan(),
+                ..perspective.clone()
+            };
+            camera.translation = camera_settings.orbit_target + camera.back() * distance;
+            *projection = orthographic_matching(&camera_settings, &flat);
+            let Projection::Orthographic(orthographic) = &mut *projection else {
+                continue;
+            };
+            orthographic.near = near;
+            orthographic.far = far;
+        }
+    }
+}
+
+/// Switch each orthographic projection over to its camera's `framing_policy`, keeping the view it
+/// shows, so the scene stays framed without stretching when the window is resized.
+///
+/// A `ScalingMode::Fixed` view carries its size, like the 8 by 8 one the example starts with. Any
+/// other is sized from the area it showed last frame.
+fn apply_framing_policy(mut cameras: Query<(&CameraSettings, &mut Projection), With<OrbitCamera>>) {
+    for (camera_settings, mut projection) in &mut cameras {
+        let Projection::Orthographic(orthographic) = &*projection else {
+            continue;
+        };
+        let policy = camera_settings.framing_policy;
+        if policy.matches(&orthographic.scaling_mode) {
+            continue;
+        }
+
+        let size = match orthographic.scaling_mode {
+            ScalingMode::Fixed { width, height } => Vec2::new(width, height),
+            _ => orthographic.area.size() / orthographic.scale,
+        };
+        if let Projection::Orthographic(orthographic) = &mut *projection {
+            orthographic.scaling_mode = policy.scaling_mode(size.x, size.y);
+        }
+    }
+}
+
+/// With `ortho_zoom_scales_view` set, scale each orthographic view by how much the orbit distance
+/// changed since last frame, so dollying in zooms in like it would in perspective. Scaling about
+/// the view's center sits well with zooming toward the cursor, which moves the target toward what
+/// is under the cursor by the same ratio.
+fn scale_ortho_view(mut cameras: Query<(&mut CameraSettings, &mut Projection), With<OrbitCamera>>) {
+    for (mut camera_settings, mut projection) in &mut cameras {
+        let distance = camera_settings.orbit_distance;
+        let orthographic = camera_settings.ortho_zoom_scales_view
+            && matches!(*projection, Projection::Orthographic(_));
+        if !orthographic {
+            if camera_settings.ortho_zoom_distance.is_some() {
+                camera_settings.ortho_zoom_distance = None;
+            }
+            continue;
+        }
+
+        // The first frame in orthographic just records the distance, keeping the view it was
+        // switched to.
+        if let Some(previous) = camera_settings.ortho_zoom_distance {
+            if previous > 0.0 && previous != distance {
+                if let Projection::Orthographic(orthographic) = &mut *projection {
+                    orthographic.scale *= distance / previous;
+                }
+            }
+        }
+        if camera_settings.ortho_zoom_distance != Some(distance) {
+            camera_settings.ortho_zoom_distance = Some(distance);
+        }
+    }
+}
+
+/// Set each camera's projection to use its `near` and `far` planes, including after switching
+/// projection.
+fn apply_clip_planes(mut cameras: Query<(&CameraSettings, &mut Projection), With<OrbitCamera>>) {
+    for (camera_settings, mut projection) in &mut cameras {
+        let (near, far) = camera_settings.clip_planes();
+        let current = match &*projection {
+            Projection::Perspective(perspective) => (perspective.near, perspective.far),
+            Projection::Orthographic(orthographic) => (orthographic.near, orthographic.far),
+        };
+        if current == (near, far) {
+            continue;
+        }
+        match &mut *projection {
+            Projection::Perspective(perspective) => {
+                perspective.near = near;
+                perspective.far = far;
+            }
+            Projection::Orthographic(orthographic) => {
+                orthographic.near = near;
+                orthographic.far = far;
+            }
+        }
+    }
+}
+
+/// The clip plane bindings, by default minus and equals for near and the square brackets for far,
+/// move a plane in or out by a quarter, to find the range a scene needs.
+fn nudge_clip_planes(
+    mut cameras: Query<&mut CameraSettings, With<OrbitCamera>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+) {
+    const STEP: f32 = 1.25;
+
+    let pressed = |binding: &Binding| binding.just_pressed(&mouse_buttons, &keys);
+    let near_scale = if pressed(&bindings.near_in) {
+        1.0 / STEP
+    } else if pressed(&bindings.near_out) {
+        STEP
+    } else {
+        1.0
+    };
+    let far_scale = if pressed(&bindings.far_in) {
+        1.0 / STEP
+    } else if pressed(&bindings.far_out) {
+        STEP
+    } else {
+        1.0
+    };
+    if near_scale == 1.0 && far_scale == 1.0 {
+        return;
+    }
+
+    for mut camera_settings in &mut cameras {
+        // Start from the planes actually in use, so nudging an invalid pair fixes it first.
+        let (near, far) = camera_settings.clip_planes();
+        camera_settings.near = near * near_scale;
+        camera_settings.far = far * far_scale;
+        let (near, far) = camera_settings.clip_planes();
+        info!("Clip planes: near {near}, far {far}");
+    }
+}
+
+/// Show or hide the axes drawn at the orbit target on every orbit camera.
+fn toggle_axes(
+    mut cameras: Query<&mut CameraSettings, With<OrbitCamera>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+) {
+    if !bindings.toggle_axes.just_pressed(&mouse_buttons, &keys) {
+        return;
+    }
+    for mut camera_settings in &mut cameras {
+        camera_settings.show_axes = !camera_settings.show_axes;
+    }
+}
+
+/// The drag zoom toggle, Z in `NavScheme::OneButton`, switches the orbit drag between orbiting and
+/// zooming, so zooming doesn't need a second button held.
+fn toggle_drag_zoom(
+    mut cameras: Query<&mut CameraSettings, With<OrbitCamera>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+) {
+    let Some(toggle) = bindings.toggle_drag_zoom else {
+        return;
+    };
+    if !toggle.just_pressed(&mouse_buttons, &keys) {
+        return;
+    }
+    for mut camera_settings in &mut cameras {
+        camera_settings.drag_zoom_toggled = !camera_settings.drag_zoom_toggled;
+        let action = if camera_settings.drag_zoom_toggled {
+            "zooms"
+        } else {
+            "orbits"
+        };
+        info!("Dragging now {action}");
+    }
+}
+
+fn toggle_hud(
+    mut cameras: Query<&mut CameraSettings, With<OrbitCamera>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+) {
+    if !bindings.toggle_hud.just_pressed(&mouse_buttons, &keys) {
+        return;
+    }
+    for mut camera_settings in &mut cameras {
+        camera_settings.show_hud = !camera_settings.show_hud;
+    }
+}
+
+fn spawn_hud(mut commands: Commands) {
+    commands.spawn((
+        Name::new("Camera HUD"),
+        CameraHud,
+        HideInScreenshot,
+        Text::default(),
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(12.),
+            left: Val::Px(12.),
+            ..default()
+        },
+        Visibility::Hidden,
+    ));
+    commands.spawn((
+        Name::new("Selection box"),
+        SelectionBox,
+        HideInScreenshot,
+        Node {
+            position_type: PositionType::Absolute,
+            border: UiRect::all(Val::Px(1.)),
+            ..default()
+        },
+        BorderColor(Color::WHITE),
+        BackgroundColor(Color::WHITE.with_alpha(0.1)),
+        Visibility::Hidden,
+    ));
+}
+
+/// Show the pose of every camera with `show_hud` set, one line each, hiding the HUD when there
+/// are none.
+fn camera_hud(
+    cameras: Query<(&Transform, &CameraSettings), With<OrbitCamera>>,
+    mut huds: Query<(&mut Text, &mut Visibility), With<CameraHud>>,
+) {
+    let lines: Vec<String> = cameras
+        .iter()
+        .filter(|(_, camera_settings)| camera_settings.show_hud)
+        .map(|(camera, camera_settings)| {
+            let state = CameraState::capture(camera, camera_settings);
+            let target = state.target;
+            format!(
+                "yaw {:.1}°  pitch {:.1}°  roll {:.1}°  \
+                distance {:.1}  target ({:.1}, {:.1}, {:.1})",
+                state.yaw.to_degrees(),
+                state.pitch.to_degrees(),
+                state.roll.to_degrees(),
+                state.distance,
+                target.x,
+                target.y,
+                target.z,
+            )
+        })
+        .collect();
+
+    for (mut text, mut visibility) in &mut huds {
+        let shown = if lines.is_empty() {
+            Visibility::Hidden
+        } else {
+            Visibility::Inherited
+        };
+        visibility.set_if_neq(shown);
+        let contents = lines.join("\n");
+        if text.0 != contents {
+            text.0 = contents;
+        }
+    }
+}
+
+/// Draw the world axes at the orbit target, so it is clear which way the camera is facing.
+fn draw_axes(cameras: Query<&CameraSettings, With<OrbitCamera>>, mut gizmos: Gizmos) {
+    // Fraction of the orbit distance the axes extend, keeping them the same size on screen
+    const AXES_SCALE: f32 = 0.1;
+
+    for camera_settings in &cameras {
+        if !camera_settings.show_axes {
+            continue;
+        }
+        let origin = camera_settings.orbit_target;
+        let length = camera_settings.orbit_distance * AXES_SCALE;
+        gizmos.line(origin, origin + Vec3::X * length, RED);
+        gizmos.line(origin, origin + Vec3::Y * length, LIME);
+        gizmos.line(origin, origin + Vec3::Z * length, BLUE);
+    }
+}
+
+/// Mark the orbit target while the camera is being orbited or panned, fading the marker out once
+/// it is let go.
+fn draw_focus_gizmo(
+    mut cameras: Query<&mut CameraSettings, With<OrbitCamera>>,
+    mut gizmos: Gizmos,
+    time: Res<Time>,
+) {
+    // Marker radius as a fraction of the orbit distance, keeping it the same size on screen
+    const FOCUS_GIZMO_SCALE: f32 = 0.01;
+
+    for mut camera_settings in &mut cameras {
+        let fade = if camera_settings.focus_gizmo_fade > 0.0 {
+            1.0 - camera_settings.focus_gizmo_age / camera_settings.focus_gizmo_fade
+        } else if camera_settings.focus_gizmo_age == 0.0 {
+            1.0
+        } else {
+            0.0
+        };
+        if fade > 0.0 {
+            let color = camera_settings.focus_gizmo_color;
+            gizmos.sphere(
+                Isometry3d::from_translation(camera_settings.orbit_target),
+                camera_settings.orbit_distance * FOCUS_GIZMO_SCALE,
+                color.with_alpha(color.alpha() * fade),
+            );
+        }
+        camera_settings.focus_gizmo_age += time.delta_secs();
+    }
+}
+
+/// With `highlight_hover` set, cast a ray through the cursor and outline the bounding box of the
+/// first mesh it hits, so it's clear what double-clicking would focus on.
+fn highlight_hover(
+    mut cameras: Query<(&mut CameraSettings, &Camera, &GlobalTransform), With<OrbitCamera>>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    mut ray_cast: MeshRayCast,
+    meshes: Query<(&GlobalTransform, &Aabb)>,
+    mut gizmos: Gizmos,
+) {
+    let cursor = window.cursor_position();
+    for (mut camera_settings, camera, camera_transform) in &mut cameras {
+        if !camera_settings.highlight_hover {
+            if camera_settings.hovered.is_some() {
+                camera_settings.hovered = None;
+            }
+            continue;
+        }
+
+        // Only the camera whose viewport the cursor is over looks for a hit.
+        let hovered = cursor
+            .and_then(|cursor| cursor_ray(camera, camera_transform, cursor))
+            .and_then(|ray| {
+                ray_cast
+                    .cast_ray(ray, &RayCastSettings::default())
+                    .first()
+                    .map(|(entity, _)| *entity)
+            });
+        if camera_settings.hovered != hovered {
+            camera_settings.hovered = hovered;
+        }
+
+        let Some((transform, aabb)) = hovered.and_then(|entity| meshes.get(entity).ok()) else {
+            continue;
+        };
+        // The box is drawn in the mesh's own space, so it turns with it.
+        let outline = *transform
+            * Transform::from_translation(aabb.center.into())
+                .with_scale(Vec3::from(aabb.half_extents) * 2.0);
+        gizmos.cuboid(outline, camera_settings.hover_color);
+    }
+}
+
+/// By default, pressing R animates the camera back to its starting view.
+fn reset_view(
+    mut cameras: Query<(&Transform, &mut CameraSettings), With<OrbitCamera>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+) {
+    if !bindings.reset.just_pressed(&mouse_buttons, &keys) {
+        return;
+    }
+
+    for (camera, mut camera_settings) in &mut cameras {
+        let initial_state = CameraState {
+            yaw: camera_settings.initial_yaw,
+            pitch: camera_settings.initial_pitch,
+            roll: camera_settings.initial_roll,
+            distance: camera_settings.initial_distance,
+            target: camera_settings.initial_target,
+        };
+        let easing = camera_settings.reset_easing;
+        camera_settings.transition_to_state(camera.rotation, &initial_state, easing);
+    }
+}
+
+/// By default, Ctrl and a number key saves the current view in that slot; the number key alone
+/// recalls it.
+fn viewpoints(
+    mut cameras: Query<(&Transform, &mut CameraSettings), With<OrbitCamera>>,
+    mut viewpoints: ResMut<Viewpoints>,
+    keys: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+) {
+    let Some(slot) = bindings
+        .viewpoint_slots
+        .iter()
+        .position(|key| keys.just_pressed(*key))
+    else {
+        return;
+    };
+
+    if keys.any_pressed(bindings.store_viewpoint_modifiers.iter().copied()) {
+        // The slots are shared, so with several cameras the first one's view is saved.
+        if let Some((camera, camera_settings)) = cameras.iter().next() {
+            viewpoints.slots[slot] = Some(CameraState::capture(camera, camera_settings));
+        }
+    } else if let Some(state) = viewpoints.slots[slot] {
+        // Recalling an empty slot does nothing.
+        for (camera, mut camera_settings) in &mut cameras {
+            let easing = camera_settings.preset_easing;
+            camera_settings.transition_to_state(camera.rotation, &state, easing);
+        }
+    }
+}
+
+/// Numpad 7, 1 and 3, by default, turn the camera to look at the target from the top, front and
+/// right.
+fn preset_view(
+    mut cameras: Query<(&Transform, &mut CameraSettings), With<OrbitCamera>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+) {
+    for (camera, mut camera_settings) in &mut cameras {
+        let (yaw, pitch) = if bindings.top_view.just_pressed(&mouse_buttons, &keys) {
+            // Straight down would be outside the pitch limits, so look down as far as they allow.
+            (0.0, camera_settings.pitch_range.start)
+        } else if bindings.front_view.just_pressed(&mouse_buttons, &keys) {
+            (0.0, 0.0)
+        } else if bindings.right_view.just_pressed(&mouse_buttons, &keys) {
+            (FRAC_PI_2, 0.0)
+        } else {
+            return;
+        };
+
+        // The presets are a heading and elevation, so express them in whatever order the
+        // camera's angles are read in.
+        let (yaw, pitch, roll) =
+            Quat::from_euler(EulerRot::YXZ, yaw, pitch, 0.0).to_euler(camera_settings.euler_order);
+        let state = CameraState {
+            yaw,
+            pitch,
+            roll,
+            distance: camera_settings.target_distance,
+            target: camera_settings.orbit_target,
+        };
+        let easing = camera_settings.preset_easing;
+        camera_settings.transition_to_state(camera.rotation, &state, easing);
+    }
+}
+
+fn load_camera_settings(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(CameraSettingsFile(asset_server.load(CAMERA_SETTINGS_PATH)));
+}
+
+/// Apply the settings file to every orbit camera whenever it finishes loading or changes on disk.
+///
+/// Cameras keep their settings when the file is missing or broken, which on startup means the
+/// defaults. Picking up edits while running needs Bevy's `file_watcher` feature, for example
+/// `cargo run --features bevy/file_watcher`.
+fn apply_camera_settings(
+    mut cameras: Query<&mut CameraSettings, With<OrbitCamera>>,
+    mut events: EventReader<AssetEvent<CameraSettingsAsset>>,
+    mut failures: EventReader<AssetLoadFailedEvent<CameraSettingsAsset>>,
+    assets: Res<Assets<CameraSettingsAsset>>,
+    file: Option<Res<CameraSettingsFile>>,
+) {
+    for failure in failures.read() {
+        warn!(
+            "Could not load {}, keeping the current camera settings: {}",
+            failure.path, failure.error
+        );
+    }
+
+    let Some(file) = file else {
+        return;
+    };
+    let changed = events.read().any(|event| match event {
+        AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id } => {
+            *id == file.0.id()
+        }
+        _ => false,
+    });
+    if !changed {
+        return;
+    }
+
+    let Some(tuning) = assets.get(&file.0) else {
+        return;
+    };
+    for name in CameraSettings::per_camera_fields(&tuning.fields) {
+        warn!("Ignoring {name} in {CAMERA_SETTINGS_PATH}, as each camera keeps its own");
+    }
+    for mut camera_settings in &mut cameras {
+        camera_settings.apply_tuning(&tuning.settings, &tuning.fields);
+    }
+}
+
+/// Load bookmarks saved by an earlier run. Without a readable file, all slots start empty.
+fn load_viewpoints(mut viewpoints: ResMut<Viewpoints>) {
+    let contents = match fs::read_to_string(VIEWPOINTS_PATH) {
+        Ok(contents) => contents,
+        Err(error) if error.kind() == ErrorKind::NotFound => return,
+        Err(error) => {
+            warn!("Could not read {VIEWPOINTS_PATH}: {error}");
+            return;
+        }
+    };
+
+    match ron::from_str(&contents) {
+        Ok(loaded) => *viewpoints = loaded,
+        Err(error) => warn!("Could not parse {VIEWPOINTS_PATH}: {error}"),
+    }
+}
+
+/// Load a camera path saved by an earlier run. An empty file leaves the path empty, and one that
+/// can't be read, can't be parsed or has keyframes out of order is skipped with a warning.
+fn load_path(mut path: ResMut<CameraPath>) {
+    let contents = match fs::read_to_string(CAMERA_PATH_PATH) {
+        Ok(contents) => contents,
+        Err(error) if error.kind() == ErrorKind::NotFound => return,
+        Err(error) => {
+            warn!("Could not read {CAMERA_PATH_PATH}: {error}");
+            return;
+        }
+    };
+    if contents.trim().is_empty() {
+        return;
+    }
+
+    let keyframes: Vec<(f32, CameraState)> = match ron::from_str(&contents) {
+        Ok(keyframes) => keyframes,
+        Err(error) => {
+            warn!("Could not parse {CAMERA_PATH_PATH}: {error}");
+            return;
+        }
+    };
+    // Playback looks keyframes up by time, so they have to be in order.
+    if !keyframes.is_sorted_by(|a, b| a.0 <= b.0) {
+        warn!("Keyframes in {CAMERA_PATH_PATH} are not in time order, ignoring them");
+        return;
+    }
+    path.keyframes = keyframes;
+}
+
+/// Pressing F8, by default, writes the recorded camera path to disk, so it can be played back on
+/// the next run or shared.
+fn save_path(
+    path: Res<CameraPath>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+) {
+    if !bindings.save_path.just_pressed(&mouse_buttons, &keys) {
+        return;
+    }
+    if path.keyframes.is_empty() {
+        warn!("There is no recorded camera path to save");
+        return;
+    }
+
+    let result = ron::ser::to_string_pretty(&path.keyframes, ron::ser::PrettyConfig::default())
+        .map_err(|error| error.to_string())
+        .and_then(|contents| {
+            fs::write(CAMERA_PATH_PATH, contents).map_err(|error| error.to_string())
+        });
+    match result {
+        Ok(()) => info!("Saved the camera path to {CAMERA_PATH_PATH}"),
+        Err(error) => warn!("Could not save the camera path to {CAMERA_PATH_PATH}: {error}"),
+    }
+}
+
+/// Pressing F5, by default, writes the bookmarks to disk, so they can be loaded on the next run.
+fn save_viewpoints(
+    viewpoints: Res<Viewpoints>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+) {
+    if !bindings.save_viewpoints.just_pressed(&mouse_buttons, &keys) {
+        return;
+    }
+
+    let result = ron::ser::to_string_pretty(&*viewpoints, ron::ser::PrettyConfig::default())
+        .map_err(|error| error.to_string())
+        .and_then(|contents| {
+            fs::write(VIEWPOINTS_PATH, contents).map_err(|error| error.to_string())
+        });
+    match result {
+        Ok(()) => info!("Saved viewpoints to {VIEWPOINTS_PATH}"),
+        Err(error) => warn!("Could not save viewpoints to {VIEWPOINTS_PATH}: {error}"),
+    }
+}
+
+/// Double-clicking a mesh makes it the orbit target of the camera it was clicked in, and zooms
+/// that camera so it fills the view. Clicks on UI that takes input are ignored.
+#[allow(clippy::too_many_arguments)]
+fn focus_on_click(
+    mut cameras: Query<
+        (&mut CameraSettings, &Camera, &GlobalTransform, &Projection),
+        With<OrbitCamera>,
+    >,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    bindings: Res<KeyBindings>,
+    time: Res<Time>,
+    mut last_click: Local<Option<f32>>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    mut ray_cast: MeshRayCast,
+    transforms: Query<&GlobalTransform>,
+    aabbs: Query<&Aabb>,
+    mut cycle: ResMut<FocusCycle>,
+    interactions: Query<&Interaction>,
+) {
+    if !mouse_buttons.just_pressed(bindings.focus) || ui_wants_input(&interactions) {
+        return;
+    }
+
+    // Every camera settings component has the same double-click time unless configured otherwise,
+    // so take it from the first.
+    let double_click_time = cameras.iter().next().map_or(0.0, |(camera_settings, ..)| {
+        camera_settings.double_click_time
+    });
+    let now = time.elapsed_secs();
+    let is_double_click = last_click.is_some_and(|last| now - last <= double_click_time);
+    // A third click starts a new double-click rather than completing another one.
+    *last_click = if is_double_click { None } else { Some(now) };
+    if !is_double_click {
+        return;
+    }
+
+    let Some(cursor) = window.cursor_position() else {
+        return;
+    };
+    // The ray comes from the camera whose viewport the cursor is over.
+    let Some((mut camera_settings, projection, ray)) =
+        cameras
+            .iter_mut()
+            .find_map(|(camera_settings, camera, camera_transform, projection)| {
+                if !camera_settings.receives_input {
+                    return None;
+                }
+                let ray = cursor_ray(camera, camera_transform, cursor)?;
+                Some((camera_settings, projection, ray))
+            })
+    else {
+        return;
+    };
+
+    let hit = ray_cast
+        .cast_ray(ray, &RayCastSettings::default())
+        .first()
+        .map(|(entity, _)| *entity);
+
+    // Ignore clicks on empty space, rather than jumping to the origin.
+    let Some(entity) = hit else {
+        return;
+    };
+    let Some((center, distance)) = fit_distance_for_entity(entity, &transforms, &aabbs, projection)
+    else {
+        return;
+    };
+
+    // Back off far enough for the mesh's bounding sphere to fit the view.
+    let easing = camera_settings.focus_easing;
+    camera_settings.transition_target_to(center, easing);
+    camera_settings.target_distance = distance;
+    camera_settings.zoom_anchor = None;
+    cycle.focused = Some(entity);
+}
+
+/// Pressing period, by default, moves the camera to frame the entity last focused on by
+/// double-clicking or the focus cycle, wherever it is now.
+fn frame_focused(
+    mut cameras: Query<(&mut CameraSettings, &Projection), With<OrbitCamera>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    cycle: Res<FocusCycle>,
+    transforms: Query<&GlobalTransform>,
+    aabbs: Query<&Aabb>,
+) {
+    if !bindings.frame_focused.just_pressed(&mouse_buttons, &keys) {
+        return;
+    }
+    let Some(entity) = cycle.focused else {
+        return;
+    };
+
+    for (mut camera_settings, projection) in &mut cameras {
+        let Some((center, distance)) =
+            fit_distance_for_entity(entity, &transforms, &aabbs, projection)
+        else {
+            continue;
+        };
+        let easing = camera_settings.focus_easing;
+        camera_settings.transition_target_to(center, easing);
+        camera_settings.target_distance = distance;
+        camera_settings.zoom_anchor = None;
+    }
+}
+
+/// Frames the entity a [`FocusCamera`] was triggered on, and makes it the focused entity.
+fn focus_camera(
+    trigger: Trigger<FocusCamera>,
+    mut cameras: Query<(&mut CameraSettings, &Projection), With<OrbitCamera>>,
+    transforms: Query<&GlobalTransform>,
+    aabbs: Query<&Aabb>,
+    mut cycle: ResMut<FocusCycle>,
+) {
+    let entity = trigger.entity();
+    // Triggered without a target, so there is nothing to focus on.
+    if entity == Entity::PLACEHOLDER {
+        return;
+    }
+
+    for (mut camera_settings, projection) in &mut cameras {
+        let Some((center, distance)) =
+            fit_distance_for_entity(entity, &transforms, &aabbs, projection)
+        else {
+            continue;
+        };
+        let easing = camera_settings.focus_easing;
+        camera_settings.transition_target_to(center, easing);
+        camera_settings.target_distance = distance;
+        camera_settings.zoom_anchor = None;
+        cycle.focused = Some(entity);
+    }
+}
+
+/// A box selection being dragged out in a camera's viewport, in window coordinates.
+#[derive(Debug, Clone, Copy)]
+struct BoxDrag {
+    camera: Entity,
+    start: Vec2,
+    end: Vec2,
+}
+
+/// Dragging with Alt and the left mouse, by default, draws a box, and letting go moves the camera
+/// it was drawn in to frame every mesh that lies entirely inside it on screen. A box with nothing
+/// inside, or too small to be more than a click, does nothing.
+#[allow(clippy::too_many_arguments)]
+fn box_select(
+    mut cameras: Query<
+        (
+            Entity,
+            &mut CameraSettings,
+            &Camera,
+            &GlobalTransform,
+            &Projection,
+        ),
+        With<OrbitCamera>,
+    >,
+    window: Single<&Window, With<PrimaryWindow>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    interactions: Query<&Interaction>,
+    scene: Query<(&GlobalTransform, &Aabb), With<Mesh3d>>,
+    mut selection_boxes: Query<(&mut Node, &mut Visibility), With<SelectionBox>>,
+    mut drag: Local<Option<BoxDrag>>,
+) {
+    // Smallest box, in pixels across, taken as a selection rather than a click
+    const MIN_BOX_SIZE: f32 = 4.0;
+
+    let Some(binding) = bindings.box_select else {
+        return;
+    };
+    let cursor = window.cursor_position();
+
+    if drag.is_none()
+        && binding.just_pressed(&mouse_buttons, &keys)
+        && !ui_wants_input(&interactions)
+    {
+        *drag = cursor.and_then(|cursor| {
+            let (camera, ..) = cameras.iter().find(|(_, camera_settings, camera, ..)| {
+                camera_settings.receives_input
+                    && camera
+                        .logical_viewport_rect()
+                        .is_some_and(|viewport| viewport.contains(cursor))
+            })?;
+            Some(BoxDrag {
+                camera,
+                start: cursor,
+                end: cursor,
+            })
+        });
+    }
+    let Some(current) = &mut *drag else {
+        return;
+    };
+    // Off the window, the box keeps the corner it last had.
+    if let Some(cursor) = cursor {
+        current.end = cursor;
+    }
+    let rect = Rect::from_corners(current.start, current.end);
+    let camera_entity = current.camera;
+
+    // Letting go of the modifier first still finishes the box, rather than dropping it.
+    if binding.button_pressed(&mouse_buttons, &keys) {
+        for (mut node, mut visibility) in &mut selection_boxes {
+            node.left = Val::Px(rect.min.x);
+            node.top = Val::Px(rect.min.y);
+            node.width = Val::Px(rect.width());
+            node.height = Val::Px(rect.height());
+            *visibility = Visibility::Visible;
+        }
+        return;
+    }
+
+    *drag = None;
+    for (_, mut visibility) in &mut selection_boxes {
+        *visibility = Visibility::Hidden;
+    }
+    if rect.width() < MIN_BOX_SIZE || rect.height() < MIN_BOX_SIZE {
+        return;
+    }
+    let Ok((_, mut camera_settings, camera, camera_transform, projection)) =
+        cameras.get_mut(camera_entity)
+    else {
+        return;
+    };
+    let Some(viewport) = camera.logical_viewport_rect() else {
+        return;
+    };
+
+    // A mesh is inside when every corner of its bounding box projects inside the rectangle. A
+    // corner behind the camera has no position on screen, so that mesh is left out.
+    let selected = scene.iter().filter_map(|(transform, aabb)| {
+        let center = Vec3::from(aabb.center);
+        let half_extents = Vec3::from(aabb.half_extents);
+        let inside = (0..8).all(|corner| {
+            let sign = Vec3::new(
+                if corner & 1 == 0 { -1.0 } else { 1.0 },
+                if corner & 2 == 0 { -1.0 } else { 1.0 },
+                if corner & 4 == 0 { -1.0 } else { 1.0 },
+            );
+            let point = transform.transform_point(center + half_extents * sign);
+            camera
+                .world_to_viewport(camera_transform, point)
+                .is_ok_and(|position| rect.contains(position + viewport.min))
+        });
+        inside.then(|| world_aabb(aabb, transform))
+    });
+    let Some((center, radius)) = bounding_sphere(selected) else {
+        return;
+    };
+
+    let easing = camera_settings.focus_easing;
+    camera_settings.transition_target_to(center, easing);
+    let distance = fit_distance_for_bounds(radius, projection);
+    camera_settings.target_distance = distance;
+    camera_settings.zoom_anchor = None;
+    camera_settings.fit_far_plane(radius, distance);
+}
+
+/// Pressing F, by default, or sending [`FrameAll`], moves the camera to frame every mesh in the
+/// scene, wherever they currently are.
+fn frame_all(
+    mut cameras: Query<(&mut CameraSettings, &Projection), With<OrbitCamera>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    mut requests: EventReader<FrameAll>,
+    scene: Query<(&GlobalTransform, &Aabb), With<Mesh3d>>,
+) {
+    let requested = requests.read().count() > 0;
+    if !requested && !bindings.frame_all.just_pressed(&mouse_buttons, &keys) {
+        return;
+    }
+
+    let boxes = scene
+        .iter()
+        .map(|(transform, aabb)| world_aabb(aabb, transform));
+    let Some((center, radius)) = bounding_sphere(boxes) else {
+        return;
+    };
+
+    for (mut camera_settings, projection) in &mut cameras {
+        let easing = camera_settings.focus_easing;
+        camera_settings.transition_target_to(center, easing);
+        let distance = fit_distance_for_bounds(radius, projection);
+        camera_settings.target_distance = distance;
+        camera_settings.zoom_anchor = None;
+        camera_settings.fit_far_plane(radius, distance);
+    }
+}
+
+/// Entities the focus cycle can step through: focusable ones and named meshes, never a camera.
+type FocusCandidate = (
+    Or<(With<Focusable>, (With<Name>, With<Mesh3d>))>,
+    Without<OrbitCamera>,
+);
+
+/// Pressing Tab, by default, animates the orbit target to the next [`Focusable`] entity, or the
+/// next named mesh when nothing is marked focusable. Shift+Tab goes back to the previous one.
+///
+/// The target then keeps following the entity as it moves, through `follow_target` like any other
+/// followed entity, until something else moves the target, such as panning or focusing elsewhere.
+/// Only cameras taking input this frame move, so with split viewports it's the active one.
+fn focus_cycle(
+    mut cameras: Query<&mut CameraSettings, With<OrbitCamera>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    mut cycle: ResMut<FocusCycle>,
+    candidates: Query<(Entity, &GlobalTransform, Has<Focusable>), FocusCandidate>,
+) {
+    // The binding with the modifier wins, as the plain one also fires while it's held.
+    let step = if bindings.focus_previous.just_pressed(&mouse_buttons, &keys) {
+        -1
+    } else if bindings.focus_next.just_pressed(&mouse_buttons, &keys) {
+        1
+    } else {
+        return;
+    };
+
+    let any_focusable = candidates.iter().any(|(_, _, focusable)| focusable);
+    let mut entities: Vec<Entity> = candidates
+        .iter()
+        .filter(|&(_, _, focusable)| focusable || !any_focusable)
+        .map(|(entity, _, _)| entity)
+        .collect();
+    if entities.is_empty() {
+        return;
+    }
+    entities.sort();
+
+    let count = entities.len() as isize;
+    let index = match cycle.index {
+        Some(index) => (index as isize + step).rem_euclid(count),
+        None if step > 0 => 0,
+        None => count - 1,
+    } as usize;
+    let entity = entities[index];
+    let Ok((_, transform, _)) = candidates.get(entity) else {
+        return;
+    };
+    let position = transform.translation();
+
+    cycle.index = Some(index);
+    cycle.focused = Some(entity);
+    for mut camera_settings in &mut cameras {
+        if !camera_settings.receives_input {
+            continue;
+        }
+        let easing = camera_settings.focus_easing;
+        camera_settings.transition_target_to(position, easing);
+        camera_settings.follow_target = Some(entity);
+        camera_settings.focus_gizmo_age = 0.0;
+    }
+}
+
+/// Orbit with one finger, pinch to zoom and twist two fingers to roll.
+///
+/// Finger positions are remembered between frames. Whenever a finger is added or lifted, that
+/// frame only records the new positions, so the change in finger count never reads as a sudden
+/// pinch or drag.
+fn touch_orbit(
+    mut cameras: Query<(&mut CameraSettings, Option<&Projection>), With<OrbitCamera>>,
+    touches: Res<Touches>,
+    mut previous_fingers: Local<BTreeMap<u64, Vec2>>,
+) {
+    let fingers: BTreeMap<u64, Vec2> = touches
+        .iter()
+        .map(|touch| (touch.id(), touch.position()))
+        .collect();
+    let previous = std::mem::replace(&mut *previous_fingers, fingers.clone());
+    if !fingers.keys().eq(previous.keys()) {
+        return;
+    }
+    let (current, previous): (Vec<Vec2>, Vec<Vec2>) = fingers
+        .values()
+        .copied()
+        .zip(previous.values().copied())
+        .unzip();
+
+    match current[..] {
+        [position] => {
+            // Dragging a finger turns the camera the same way dragging the mouse does.
+            let delta = previous[0] - position;
+            for (mut camera_settings, projection) in &mut cameras {
+                if !camera_settings.receives_input {
+                    continue;
+                }
+                let (pitch_speed, yaw_speed) = camera_settings.pointer_rotation_speeds(projection);
+                let mut delta_pitch = delta.y * pitch_speed;
+                let mut delta_yaw = delta.x * yaw_speed;
+                if camera_settings.invert_pitch {
+                    delta_pitch = -delta_pitch;
+                }
+                if camera_settings.invert_yaw {
+                    delta_yaw = -delta_yaw;
+                }
+                camera_settings.queued_rotation += Vec3::new(delta_yaw, delta_pitch, 0.0);
+            }
+        }
+        [first, second] => {
+            let span = second - first;
+            let previous_span = previous[1] - previous[0];
+            if span.length() <= f32::EPSILON || previous_span.length() <= f32::EPSILON {
+                return;
+            }
+            // Spreading the fingers apart zooms in, in proportion to how far they moved.
+            let pinch = previous_span.length() / span.length();
+            // Wrap the twist so crossing the ±π boundary doesn't spin the camera all the way round.
+            let twist = (span.to_angle() - previous_span.to_angle() + PI).rem_euclid(TAU) - PI;
+            for (mut camera_settings, _) in &mut cameras {
+                if !camera_settings.receives_input {
+                    continue;
+                }
+                let distance = camera_settings.target_distance * pinch;
+                camera_settings.set_distance(distance);
+                camera_settings.queued_rotation.z -= twist;
+            }
+        }
+        _ => {}
+    }
+}
+
+/// With `edge_scroll_enabled`, resting the cursor near an edge of the window turns the camera that
+/// way, faster the closer it gets. Nothing happens while any mouse button is held, so dragging
+/// near an edge doesn't also scroll.
+fn edge_scroll(
+    mut cameras: Query<&mut CameraSettings, With<OrbitCamera>>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    time: Res<Time>,
+    interactions: Query<&Interaction>,
+) {
+    if !window.focused || mouse_buttons.get_pressed().next().is_some() {
+        return;
+    }
+    let Some(cursor) = window.cursor_position() else {
+        return;
+    };
+    let size = window.size();
+
+    let ui_blocking = ui_wants_input(&interactions);
+    for mut camera_settings in &mut cameras {
+        let margin = camera_settings.edge_scroll_margin_px;
+        if !camera_settings.edge_scroll_enabled
+            || margin <= 0.0
+            || camera_settings.pointer_blocked(ui_blocking)
+        {
+            continue;
+        }
+
+        // How far into the margin the cursor is on each axis, from -1 at the left or top edge to 1
+        // at the right or bottom one.
+        let depth = |position: f32, extent: f32| {
+            if position < margin {
+                -(1.0 - position / margin)
+            } else if position > extent - margin {
+                1.0 - (extent - position) / margin
+            } else {
+                0.0
+            }
+        };
+        let push = Vec2::new(depth(cursor.x, size.x), depth(cursor.y, size.y));
+        if push == Vec2::ZERO {
+            continue;
+        }
+
+        // Turning toward the right edge is a negative yaw, and toward the top a positive pitch.
+        let turn = camera_settings.edge_scroll_speed * time.delta_secs();
+        camera_settings.queued_rotation += Vec3::new(-push.x * turn, -push.y * turn, 0.0);
+    }
+}
+
+/// The toggle mode binding switches between orbiting and flying, and the first-person one between
+/// orbiting and standing at the target.
+///
+/// Going back to orbiting from flying keeps the camera where it flew to, turning it to face the
+/// orbit target and taking the orbit distance from how far away it now is, so the camera doesn't
+/// jump. Every other switch keeps the look direction as it is.
+fn toggle_mode(
+    mut cameras: Query<(&mut Transform, &mut CameraSettings), With<OrbitCamera>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+) {
+    let toggle_fly = bindings.toggle_mode.just_pressed(&mouse_buttons, &keys);
+    let toggle_first_person = bindings
+        .toggle_first_person
+        .just_pressed(&mouse_buttons, &keys);
+    if !toggle_fly && !toggle_first_person {
+        return;
+    }
+
+    for (mut camera, mut camera_settings) in &mut cameras {
+        let previous_mode = camera_settings.mode;
+        camera_settings.mode = match (previous_mode, toggle_fly) {
+            (CameraMode::FreeFly, true) | (CameraMode::FirstPerson, false) => CameraMode::Orbit,
+            (_, true) => CameraMode::FreeFly,
+            (_, false) => CameraMode::FirstPerson,
+        };
+        camera_settings.target_transition = None;
+        camera_settings.requested_angles = None;
+        camera_settings.yaw_velocity = 0.0;
+        camera_settings.pitch_velocity = 0.0;
+        camera_settings.queued_rotation = Vec3::ZERO;
+        camera_settings.pending_rotation = Vec3::ZERO;
+        camera_settings.rotation_velocity = Vec3::ZERO;
+
+        if previous_mode == CameraMode::FreeFly && camera_settings.mode == CameraMode::Orbit {
+            let target = camera_settings.orbit_target;
+            let distance = camera.translation.distance(target);
+            if distance > f32::EPSILON {
+                camera.look_at(target, Vec3::Y);
+            }
+            // A distance outside the allowed range eases back into it, like any other zoom.
+            camera_settings.orbit_distance = distance;
+            camera_settings.distance_velocity = 0.0;
+            camera_settings.set_distance(distance);
+        }
+    }
+}
+
+/// F6, by default, starts recording a new camera path or stops the recording, and F7 plays the
+/// recorded path back or stops it. Starting one stops the other.
+fn path_controls(
+    mut path: ResMut<CameraPath>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+) {
+    if bindings
+        .toggle_recording
+        .just_pressed(&mouse_buttons, &keys)
+    {
+        if let PathState::Recording { .. } = path.state {
+            path.state = PathState::Idle;
+            info!(
+                "Recorded {} camera keyframes over {:.1}s",
+                path.keyframes.len(),
+                path.duration()
+            );
+        } else {
+            path.keyframes.clear();
+            path.state = PathState::Recording { elapsed: 0.0 };
+            info!("Recording the camera path");
+        }
+    }
+
+    if bindings.toggle_playback.just_pressed(&mouse_buttons, &keys) {
+        if let PathState::Playing { .. } = path.state {
+            path.state = PathState::Idle;
+        } else if path.keyframes.is_empty() {
+            warn!("There is no recorded camera path to play back");
+        } else {
+            path.state = PathState::Playing { elapsed: 0.0 };
+        }
+    }
+}
+
+/// While recording, store the pose of the first orbit camera every `sample_interval` seconds.
+fn record_path(
+    cameras: Query<(&Transform, &CameraSettings), With<OrbitCamera>>,
+    mut path: ResMut<CameraPath>,
+    time: Res<Time>,
+) {
+    let PathState::Recording { elapsed } = path.state else {
+        return;
+    };
+    let Some((camera, camera_settings)) = cameras.iter().next() else {
+        return;
+    };
+
+    let due = path
+        .keyframes
+        .last()
+        .is_none_or(|(last, _)| elapsed - last >= path.sample_interval);
+    if due {
+        let state = CameraState::capture(camera, camera_settings);
+        path.keyframes.push((elapsed, state));
+    }
+    path.state = PathState::Recording {
+        elapsed: elapsed + time.delta_secs(),
+    };
+}
+
+/// While playing back, move every orbit camera along the recorded path, timed by its
+/// `playback_easing`, taking over from any other motion until the end of the recording.
+fn play_path(
+    mut cameras: Query<(&mut Transform, &mut CameraSettings), With<OrbitCamera>>,
+    mut path: ResMut<CameraPath>,
+    time: Res<Time>,
+) {
+    let PathState::Playing { elapsed } = path.state else {
+        return;
+    };
+    let duration = path.duration();
+    let progress = if duration > 0.0 {
+        (elapsed / duration).min(1.0)
+    } else {
+        1.0
+    };
+    if path.keyframes.is_empty() {
+        path.state = PathState::Idle;
+        return;
+    }
+
+    for (mut camera, mut camera_settings) in &mut cameras {
+        let time = ease(camera_settings.playback_easing, progress) * duration;
+        let Some((rotation, distance, target)) = path.sample(time, camera_settings.euler_order)
+        else {
+            continue;
+        };
+        camera_settings.stop_motion();
+        camera_settings.set_orbit_target(target);
+        camera_settings.orbit_distance = distance;
+        camera_settings.target_distance = distance;
+        *camera = orbit_transform(camera_settings.orbit_target, rotation, distance);
+    }
+
+    path.state = if progress < 1.0 {
+        PathState::Playing {
+            elapsed: elapsed + time.delta_secs(),
+        }
+    } else {
+        PathState::Idle
+    };
+}
+
+/// Look around by dragging with the orbit binding in `CameraMode::FreeFly` and
+/// `CameraMode::FirstPerson`.
+///
+/// When flying, the fly keys move the camera relative to the view. In first person, the camera
+/// instead stays at the orbit target, so panning or focusing on an object moves it there.
+fn free_look(
+    mut cameras: Query<(&mut Transform, &CameraSettings), With<OrbitCamera>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    mouse_motion: Res<AccumulatedMouseMotion>,
+    keys: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    time: Res<Time>,
+) {
+    let looking = bindings.drag_pressed(&bindings.orbit, &mouse_buttons, &keys);
+    let axis = |positive: KeyCode, negative: KeyCode| {
+        f32::from(keys.pressed(positive)) - f32::from(keys.pressed(negative))
+    };
+    let forward = axis(bindings.fly_forward, bindings.fly_back);
+    let right = axis(bindings.fly_right, bindings.fly_left);
+    let up = axis(bindings.fly_up, bindings.fly_down);
+
+    for (mut camera, camera_settings) in &mut cameras {
+        if camera_settings.mode == CameraMode::Orbit || !camera_settings.receives_input {
+            continue;
+        }
+        let precision = camera_settings.precision_scale(&keys, &bindings);
+
+        if looking {
+            // The same mouse motion turns the view the same way it orbits the camera.
+            let delta = -mouse_motion.delta;
+            let (pitch_speed, yaw_speed) = camera_settings.rotation_speeds();
+            let mut input = OrbitInput {
+                delta_yaw: delta.x * yaw_speed * precision,
+                delta_pitch: delta.y * pitch_speed * precision,
+                ..default()
+            };
+            if camera_settings.invert_pitch {
+                input.delta_pitch = -input.delta_pitch;
+            }
+            if camera_settings.invert_yaw {
+                input.delta_yaw = -input.delta_yaw;
+            }
+            camera.rotation = turn(camera.rotation, &input, camera_settings);
+        }
+
+        if camera_settings.mode != CameraMode::Orbit {
+            camera.translation = camera_settings.orbit_target;
+            continue;
+        }
+
+        // Up and down are always vertical, so looking down while flying forward still descends.
+        let direction = camera.forward() * forward + camera.right() * right + Vec3::Y * up;
+        camera.translation += direction.normalize_or_zero()
+            * camera_settings.fly_speed
+            * precision
+            * time.delta_secs();
+    }
+}
+
+/// Run condition for `orbit`: whether there is any input it could respond to, or any camera is
+/// still animating or out of place.
+///
+/// Skipping `orbit` otherwise leaves camera transforms untouched when nothing is happening, so
+/// they don't show up as changed.
+fn orbit_needed(
+    cameras: Query<(&Transform, &CameraSettings), With<OrbitCamera>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    mouse_motion: Res<AccumulatedMouseMotion>,
+    mouse_scroll: Res<AccumulatedMouseScroll>,
+    keys: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    touches: Res<Touches>,
+) -> bool {
+    // Differences smaller than this are too small to see, so they don't need updating.
+    const SETTLED_EPSILON: f32 = 1e-4;
+
+    let any_input = mouse_motion.delta != Vec2::ZERO
+        || mouse_scroll.delta != Vec2::ZERO
+        || mouse_buttons.get_pressed().next().is_some()
+        || keys.get_pressed().next().is_some()
+        || touches.iter().next().is_some()
+        || gamepads.iter().any(|gamepad| {
+            gamepad.right_stick() != Vec2::ZERO || gamepad.get_pressed().next().is_some()
+        });
+    if any_input {
+        return true;
+    }
+
+    cameras.iter().any(|(camera, camera_settings)| {
+        if camera_settings.mode != CameraMode::Orbit {
+            return false;
+        }
+        // The turntable needs the idle timer to keep counting, so it always runs.
+        let animating = camera_settings.target_transition.is_some()
+            || camera_settings.yaw_velocity != 0.0
+            || camera_settings.pitch_velocity != 0.0
+            || camera_settings.enable_turntable
+            || camera_settings.requested_angles.is_some()
+            || camera_settings.queued_rotation != Vec3::ZERO
+            || camera_settings.key_ramp != Vec2::ZERO
+            || camera_settings.pending_rotation != Vec3::ZERO
+            || camera_settings.follow_target.is_some()
+            || camera_settings.bank_roll != 0.0
+            || (camera_settings.orbit_distance - camera_settings.target_distance).abs()
+                > SETTLED_EPSILON
+            || ((camera_settings.auto_level_roll || camera_settings.lock_roll)
+                && roll_angle(camera.rotation, camera_settings.allow_full_pitch)
+                    .is_some_and(|roll| roll.abs() > SETTLED_EPSILON));
+        // Catches the target or distance being changed directly, such as by panning.
+        let expected = orbit_transform(
+            camera_settings.orbit_target,
+            camera.rotation,
+            camera_settings.orbit_distance,
+        );
+        let out_of_place = camera.translation.distance(expected.translation) > SETTLED_EPSILON;
+        animating || out_of_place
+    })
+}
+
+/// Turn, zoom and move every orbit camera in `CameraMode::Orbit` from this frame's input.
+///
+/// Systems that move a followed entity should run `.before(orbit)`, or before [`CameraControlSet`],
+/// so the orbit target is where the entity is this frame rather than last frame.
+///
+/// [`CameraControlPlugin`] adds this along with everything else, which needs `DefaultPlugins`. On
+/// its own it only reads input, time and a few of this crate's resources, so a headless app can run
+/// it to drive cameras directly, for example in tests. Add `MinimalPlugins`, init [`KeyBindings`],
+/// `ButtonInput<MouseButton>`, `ButtonInput<KeyCode>`, `AccumulatedMouseMotion` and
+/// `AccumulatedMouseScroll`, and add the [`CameraMoved`] event. Then set the input resources before
+/// each update. Leave out `InputPlugin`, which would clear them before `orbit` sees them.
+#[allow(clippy::too_many_arguments)]
+pub fn orbit(
+    mut cameras: Query<
+        (
+            Entity,
+            &mut Transform,
+            &mut CameraSettings,
+            Option<&Projection>,
+        ),
+        With<OrbitCamera>,
+    >,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    mouse_motion: Res<AccumulatedMouseMotion>,
+    mouse_scroll: Res<AccumulatedMouseScroll>,
+    keys: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    gamepads: Query<&Gamepad>,
+    time: Res<Time>,
+    mut camera_moved: EventWriter<CameraMoved>,
+    interactions: Query<&Interaction>,
+    followed: Query<(&Transform, Option<&Parent>), Without<OrbitCamera>>,
+    global_transforms: Query<&GlobalTransform>,
+) {
+    // Below this angular velocity, in radians per second, the momentum is considered stopped.
+    const INERTIA_EPSILON: f32 = 0.001;
+    // Smaller changes to the pose than this don't count as the camera moving.
+    const MOVED_EPSILON: f32 = 1e-5;
+    // Matches `orbit_needed`, so whatever is left to ease in then is applied at once, before the
+    // camera stops being updated.
+    const SETTLED_EPSILON: f32 = 1e-4;
+
+    let any_device_input = mouse_motion.delta != Vec2::ZERO
+        || mouse_scroll.delta != Vec2::ZERO
+        || mouse_buttons.get_pressed().next().is_some()
+        || keys.get_pressed().next().is_some()
+        || gamepads
+            .iter()
+            .any(|gamepad| gamepad.get_pressed().next().is_some());
+
+    let ui_blocking = ui_wants_input(&interactions);
+    for (entity, mut camera, mut camera_settings, projection) in &mut cameras {
+        if camera_settings.mode != CameraMode::Orbit {
+            continue;
+        }
+        let previous_state = CameraState::capture(&camera, &camera_settings);
+
+        let mut delta = Vec2 { x: 0.0, y: 0.0 };
+        //
+        let mut delta_roll = 0.0;
+
+        // Dragging a slider or pressing a button shouldn't turn the camera as well.
+        let blocked = camera_settings.pointer_blocked(ui_blocking);
+        // While the orbit drag is toggled to zoom, `zoom` handles it instead.
+        let dragging = !blocked
+            && !camera_settings.drag_zoom_toggled
+            && bindings.drag_pressed(&bindings.orbit, &mouse_buttons, &keys);
+        let dt = time.delta_secs();
+        if dragging {
+            delta = -mouse_motion.delta;
+
+            // Filtering the rate of motion rather than each frame's delta, with a blend factor from
+            // an exponential decay, smooths the same amount at any frame rate.
+            if camera_settings.mouse_smoothing > 0.0 && dt > 0.0 {
+                let blend = 1.0 - (-dt / camera_settings.mouse_smoothing).exp();
+                camera_settings.smoothed_mouse_motion = camera_settings
+                    .smoothed_mouse_motion
+                    .lerp(delta / dt, blend);
+                delta = camera_settings.smoothed_mouse_motion * dt;
+            }
+        } else {
+            camera_settings.smoothed_mouse_motion = Vec2::ZERO;
+        }
+        if !blocked
+            && !camera_settings.lock_roll
+            && bindings
+                .roll
+                .is_some_and(|roll| bindings.drag_pressed(&roll, &mouse_buttons, &keys))
+        {
+            delta_roll += 1.0;
+        }
+
+        // Mouse motion is one of the few inputs that should not be multiplied by delta time,
+        // as we are already receiving the full movement since the last frame was rendered.
+        // Multiplying by delta time here would make the movement slower that it should be.
+        // The precision modifier only scales this frame's deltas, so full speed returns as soon as
+        // it is released.
+        let precision = camera_settings.precision_scale(&keys, &bindings);
+        let (pitch_speed, yaw_speed) = camera_settings.pointer_rotation_speeds(projection);
+        let mut delta_pitch = delta.y * pitch_speed * precision;
+        let mut delta_yaw = delta.x * yaw_speed * precision;
+        if camera_settings.invert_pitch {
+            delta_pitch = -delta_pitch;
+        }
+        if camera_settings.invert_yaw {
+            delta_yaw = -delta_yaw;
+        }
+
+        // Conversely, we DO need to factor in delta time for mouse button inputs.
+        delta_roll *= camera_settings.roll_speed * precision * time.delta_secs();
+
+        // The same goes for a held gamepad stick. Pushing it up or right turns the camera like
+        // moving the mouse that way does.
+        let stick = gamepads
+            .iter()
+            .filter(|_| camera_settings.receives_input)
+            .map(|gamepad| {
+                let stick =
+                    radial_deadzone(gamepad.right_stick(), camera_settings.gamepad_deadzone);
+                response_curve(stick, camera_settings.gamepad_curve)
+            })
+            .sum::<Vec2>();
+        let stick_turning = stick != Vec2::ZERO;
+
+        // Held orbit keys ramp each axis up to full speed and back down rather than switching it
+        // on and off, and turn the camera the same way as pushing the stick.
+        let axis = |positive: KeyCode, negative: KeyCode| {
+            f32::from(keys.pressed(positive)) - f32::from(keys.pressed(negative))
+        };
+        let key_axes = if camera_settings.receives_input {
+            Vec2::new(
+                axis(bindings.orbit_right, bindings.orbit_left),
+                axis(bindings.orbit_up, bindings.orbit_down),
+            )
+        } else {
+            Vec2::ZERO
+        };
+        let (attack, release) = (
+            camera_settings.key_attack_time,
+            camera_settings.key_release_time,
+        );
+        let ramp = &mut camera_settings.key_ramp;
+        ramp.x = ramp_toward(ramp.x, key_axes.x, attack, release, dt);
+        ramp.y = ramp_toward(ramp.y, key_axes.y, attack, release, dt);
+        let key_ramp = *ramp;
+        delta_yaw -= key_ramp.x * camera_settings.key_yaw_speed * precision * dt;
+        delta_pitch += key_ramp.y * camera_settings.key_pitch_speed * precision * dt;
+
+        // Touch gestures were already converted to angles by `touch_orbit`.
+        let queued = std::mem::take(&mut camera_settings.queued_rotation);
+        delta_yaw -= stick.x * camera_settings.gamepad_yaw_speed * precision * time.delta_secs();
+        delta_pitch +=
+            stick.y * camera_settings.gamepad_pitch_speed * precision * time.delta_secs();
+
+        // While dragging, remember how fast the camera is turning. Once released, keep turning at
+        // that rate, decaying it exponentially so the slowdown is the same at any frame rate.
+        if !camera_settings.enable_inertia || camera_settings.reduce_motion {
+            camera_settings.yaw_velocity = 0.0;
+            camera_settings.pitch_velocity = 0.0;
+        } else if dragging {
+            if dt > 0.0 {
+                camera_settings.yaw_velocity = delta_yaw / dt;
+                camera_settings.pitch_velocity = delta_pitch / dt;
+            }
+        } else {
+            let decay = (-camera_settings.inertia_damping * dt).exp();
+            camera_settings.yaw_velocity *= decay;
+            camera_settings.pitch_velocity *= decay;
+            if camera_settings.yaw_velocity.abs() < INERTIA_EPSILON {
+                camera_settings.yaw_velocity = 0.0;
+            }
+            if camera_settings.pitch_velocity.abs() < INERTIA_EPSILON {
+                camera_settings.pitch_velocity = 0.0;
+            }
+            delta_yaw += camera_settings.yaw_velocity * dt;
+            delta_pitch += camera_settings.pitch_velocity * dt;
+        }
+        delta_yaw += queued.x;
+        delta_pitch += queued.y;
+        if !camera_settings.lock_roll {
+            delta_roll += queued.z;
+        }
+
+        // Any input restarts the idle timer, which stops the turntable straight away.
+        let device_input = any_device_input && camera_settings.receives_input;
+        if device_input || stick_turning || key_ramp != Vec2::ZERO || queued != Vec3::ZERO {
+            camera_settings.idle_time = 0.0;
+        } else {
+            camera_settings.idle_time += dt;
+        }
+        let turntable_step = if camera_settings.enable_turntable
+            && !camera_settings.reduce_motion
+            && camera_settings.idle_time >= camera_settings.idle_timeout
+        {
+            camera_settings.turntable_rpm * TAU / 60.0 * dt
+        } else {
+            0.0
+        };
+
+        // Track the followed entity, with a spring that lets the target lag behind a fast move
+        // and catch up without overshooting. While a transition is still bringing the target over,
+        // it is steered to where the entity is now instead. If the entity is gone, settle on
+        // where it was last seen.
+        //
+        // `GlobalTransform` isn't updated until after `Update`, so reading it alone would trail a
+        // frame behind. Its local `Transform` is current as long as whatever moves it runs before
+        // `orbit`, like the example's `rotate`. A parent that moved this frame still lags, though.
+        if let Some(entity) = camera_settings.follow_target {
+            match followed.get(entity) {
+                Ok((transform, parent)) => {
+                    let position =
+                        match parent.and_then(|parent| global_transforms.get(parent.get()).ok()) {
+                            Some(parent_transform) => {
+                                parent_transform.transform_point(transform.translation)
+                            }
+                            None => transform.translation,
+                        };
+                    if let Some(transition) = &mut camera_settings.target_transition {
+                        transition.desired_target = position;
+                    } else {
+                        let current = camera_settings.orbit_target;
+                        let smooth_time = camera_settings.follow_smooth_time;
+                        let target = smooth_damp_vec3(
+                            current,
+                            position,
+                            &mut camera_settings.follow_velocity,
+                            smooth_time,
+                            dt,
+                        );
+                        camera_settings.set_orbit_target(target);
+                    }
+                    camera_settings.follow_position = Some(position);
+                }
+                Err(_) => {
+                    warn!(
+                        "Followed entity {entity} no longer exists, staying where it was last seen"
+                    );
+                    camera_settings.follow_target = None;
+                    camera_settings.follow_velocity = Vec3::ZERO;
+                    if let Some(position) = camera_settings.follow_position.take() {
+                        let easing = camera_settings.focus_easing;
+                        camera_settings.transition_target_to(position, easing);
+                    }
+                }
+            }
+        }
+
+        // Ease the distance toward the zoom target.
+        let previous_distance = camera_settings.orbit_distance;
+        let target_distance = camera_settings.target_distance;
+        let zoom_smoothing = camera_settings.zoom_smoothing;
+        let mut distance = smooth_damp(
+            previous_distance,
+            target_distance,
+            &mut camera_settings.distance_velocity,
+            zoom_smoothing,
+            dt,
+        );
+        if (distance - target_distance).abs() < SETTLED_EPSILON {
+            distance = target_distance;
+            camera_settings.distance_velocity = 0.0;
+        }
+
+        // Scaling both the target and the camera about the anchor by the same ratio as the
+        // distance keeps the anchor on the same line of sight, so it stays put under the cursor.
+        if let Some(anchor) = camera_settings.zoom_anchor {
+            let scale = distance / previous_distance;
+            let target = anchor + (camera_settings.orbit_target - anchor) * scale;
+            camera_settings.set_orbit_target(target);
+        }
+
+        // Turning the camera by hand takes over from a transition that was turning it.
+        let turning_by_hand =
+            dragging || stick_turning || queued != Vec3::ZERO || delta_roll != 0.0;
+        if let Some(transition) = &mut camera_settings.target_transition {
+            if turning_by_hand {
+                transition.rotation = None;
+            }
+        }
+        if turning_by_hand {
+            camera_settings.focus_gizmo_age = 0.0;
+        }
+        let transition_rotation = camera_settings.update_target_transition(time.delta_secs());
+
+        // While snapping, keep track of where the drag would have turned the camera and jump
+        // straight to the nearest step from there, so the camera clicks between steps.
+        let snap_angle = camera_settings.snap_angle;
+        let snapped_yaw = if dragging && snap_angle > 0.0 && keys.pressed(bindings.snap) {
+            let forward = camera.forward();
+            let yaw = f32::atan2(-forward.x, -forward.z);
+            let free_yaw = camera_settings.snap_yaw.unwrap_or(yaw) + delta_yaw;
+            camera_settings.snap_yaw = Some(free_yaw);
+            camera_settings.pending_rotation.x = 0.0;
+            camera_settings.rotation_velocity.x = 0.0;
+            let snapped = (free_yaw / snap_angle).round() * snap_angle;
+            Some((snapped - yaw + PI).rem_euclid(TAU) - PI)
+        } else {
+            camera_settings.snap_yaw = None;
+            None
+        };
+        let delta_yaw = if snapped_yaw.is_some() {
+            0.0
+        } else {
+            delta_yaw
+        };
+
+        // Input sets how far the camera should turn and the spring eases it round, so it settles
+        // without overshooting, the same way at any frame rate.
+        camera_settings.pending_rotation += Vec3::new(delta_yaw, delta_pitch, delta_roll);
+        let rotate_smoothing = camera_settings.rotate_smoothing;
+        let CameraSettings {
+            pending_rotation,
+            rotation_velocity,
+            ..
+        } = &mut *camera_settings;
+        let step = smooth_damp_offset(
+            pending_rotation,
+            rotation_velocity,
+            rotate_smoothing,
+            dt,
+            SETTLED_EPSILON,
+        );
+        let (delta_pitch, mut delta_roll) = (step.y, step.z);
+        let delta_yaw = snapped_yaw.unwrap_or(step.x);
+
+        // Level out gradually, but only while nothing else is rolling the camera. Banking is left
+        // alone, as it levels out by itself.
+        if camera_settings.auto_level_roll
+            && camera_settings.pending_rotation.z == 0.0
+            && camera_settings.requested_angles.is_none()
+        {
+            if let Some(roll) = roll_angle(camera.rotation, camera_settings.allow_full_pitch) {
+                let blend = 1.0 - (-camera_settings.roll_return_speed * dt).exp();
+                delta_roll -= (roll - camera_settings.bank_roll) * blend;
+            }
+        }
+
+        // Bank into the yaw this frame is turning by. Only the change in bank is applied, so it
+        // adds to manual roll rather than replacing it, and comes back off as the yaw stops.
+        let bank_factor = if camera_settings.reduce_motion {
+            0.0
+        } else {
+            camera_settings.bank_factor
+        };
+        if !camera_settings.lock_roll && (bank_factor != 0.0 || camera_settings.bank_roll != 0.0) {
+            let yaw_rate = if dt > 0.0 { delta_yaw / dt } else { 0.0 };
+            let max_bank = camera_settings.max_bank_angle.abs();
+            let desired = (-yaw_rate * bank_factor).clamp(-max_bank, max_bank);
+            let previous = camera_settings.bank_roll;
+            let bank_smooth_time = camera_settings.bank_smooth_time;
+            let mut bank = smooth_damp(
+                previous,
+                desired,
+                &mut camera_settings.bank_velocity,
+                bank_smooth_time,
+                dt,
+            );
+            if desired == 0.0 && bank.abs() < SETTLED_EPSILON {
+                bank = 0.0;
+                camera_settings.bank_velocity = 0.0;
+            }
+            camera_settings.bank_roll = bank;
+            delta_roll += bank - previous;
+        }
+
+        let input = OrbitInput {
+            delta_yaw,
+            delta_pitch,
+            delta_roll,
+            delta_distance: distance - previous_distance,
+        };
+        let mut transform = match transition_rotation {
+            Some(rotation) => orbit_transform(camera_settings.orbit_target, rotation, distance),
+            None => next_orbit_transform(&camera, &camera_settings, input),
+        };
+        // The turntable turns about its own axis, which may be tilted, and skips the smoothing so
+        // it keeps an exact rate.
+        if turntable_step != 0.0 {
+            let axis = camera_settings.turntable_axis.normalize_or(Vec3::Y);
+            let rotation = Quat::from_axis_angle(axis, turntable_step) * transform.rotation;
+            transform = orbit_transform(camera_settings.orbit_target, rotation, distance);
+        }
+        // Anything else that rolled the camera, like a saved view or a transition, is undone too.
+        // Rolling about the view direction leaves the camera where it is.
+        if camera_settings.lock_roll {
+            if let Some(roll) = roll_angle(transform.rotation, camera_settings.allow_full_pitch) {
+                transform.rotation =
+                    (transform.rotation * Quat::from_rotation_z(-roll)).normalize();
+            }
+        }
+        camera.rotation = transform.rotation;
+        camera.translation = transform.translation;
+        if let Some(min_height) = camera_settings.min_camera_height {
+            raise_to_floor(&mut camera, camera_settings.orbit_target, min_height);
+        }
+        camera_settings.orbit_distance = distance;
+        camera_settings.requested_angles = None;
+
+        let state = CameraState::capture(&camera, &camera_settings);
+        if state.differs_from(&previous_state, MOVED_EPSILON) {
+            camera_moved.send(CameraMoved {
+                camera: entity,
+                yaw: state.yaw,
+                pitch: state.pitch,
+                roll: state.roll,
+                distance: state.distance,
+                target: state.target,
+            });
+        }
+    }
+}
+
+/// While a mouse drag binding is held, lock and hide the cursor so it can't wander off the window.
+///
+/// The cursor is put back where the drag started once it ends, rather than wherever the locked
+/// cursor ended up.
+fn lock_cursor(
+    cameras: Query<&CameraSettings, With<OrbitCamera>>,
+    mut window: Single<&mut Window, With<PrimaryWindow>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    interactions: Query<&Interaction>,
+    mut drag_start: Local<Option<Vec2>>,
+) {
+    let enabled = cameras
+        .iter()
+        .any(|camera_settings| camera_settings.lock_cursor_while_dragging);
+    let dragging = [
+        Some(bindings.orbit),
+        bindings.roll,
+        Some(bindings.pan),
+        bindings.zoom,
+    ]
+    .into_iter()
+    .flatten()
+    .any(|binding| bindings.drag_pressed(&binding, &mouse_buttons, &keys));
+
+    match *drag_start {
+        None if enabled && dragging && !ui_wants_input(&interactions) => {
+            let Some(position) = window.cursor_position() else {
+                return;
+            };
+            *drag_start = Some(position);
+            window.cursor_options.grab_mode = CursorGrabMode::Locked;
+            window.cursor_options.visible = false;
+        }
+        Some(position) if !enabled || !dragging => {
+            *drag_start = None;
+            window.cursor_options.grab_mode = CursorGrabMode::None;
+            window.cursor_options.visible = true;
+            window.set_cursor_position(Some(position));
+        }
+        _ => {}
+    }
+}
+
+/// Save a screenshot of the primary window to a file named after the current time, hiding any
+/// `HideInScreenshot` nodes for the frame it is captured in.
+///
+/// Presses while a screenshot is still being captured are ignored, and two in the same millisecond
+/// get different names, so one never overwrites another.
+fn screenshot(
+    mut commands: Commands,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    pending: Query<(), With<Screenshot>>,
+    mut hidden: Query<(Entity, &mut Visibility), With<HideInScreenshot>>,
+    mut last_stamp: Local<u128>,
+) {
+    let pressed = bindings
+        .screenshot
+        .iter()
+        .any(|binding| binding.just_pressed(&mouse_buttons, &keys));
+    if !pressed || !pending.is_empty() {
+        return;
+    }
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_millis());
+    let stamp = now.max(*last_stamp + 1);
+    *last_stamp = stamp;
+
+    for (entity, mut visibility) in &mut hidden {
+        commands
+            .entity(entity)
+            .insert(HiddenForScreenshot(*visibility));
+        *visibility = Visibility::Hidden;
+    }
+    // `save_to_disk` logs where the file went.
+    commands
+        .spawn(Screenshot::primary_window())
+        .observe(save_to_disk(format!("screenshot-{stamp}.png")))
+        .observe(restore_after_screenshot);
+}
+
+/// Put back the nodes `screenshot` hid, once the capture is done.
+fn restore_after_screenshot(
+    _trigger: Trigger<ScreenshotCaptured>,
+    mut commands: Commands,
+    mut hidden: Query<(Entity, &HiddenForScreenshot, &mut Visibility)>,
+) {
+    for (entity, previous, mut visibility) in &mut hidden {
+        *visibility = previous.0;
+        commands.entity(entity).remove::<HiddenForScreenshot>();
+    }
+}
+
+/// Pressing the backquote key, by default, hands input to the next orbit camera in render order,
+/// making it the [`ActiveCamera`].
+///
+/// The camera giving up input drops its momentum so it holds still, and the one taking over
+/// forgets the mouse motion it was last smoothing, so it doesn't lurch on its first frame.
+fn next_camera(
+    mut commands: Commands,
+    mut cameras: Query<(Entity, &Camera, &mut CameraSettings), With<OrbitCamera>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    active: Option<Res<ActiveCamera>>,
+) {
+    if !bindings.next_camera.just_pressed(&mouse_buttons, &keys) {
+        return;
+    }
+    let mut order: Vec<(isize, Entity)> = cameras
+        .iter()
+        .map(|(entity, camera, _)| (camera.order, entity))
+        .collect();
+    order.sort();
+    let current =
+        active.and_then(|active| order.iter().position(|(_, entity)| *entity == active.0));
+    let next = match current {
+        Some(index) => (index + 1) % order.len(),
+        None => 0,
+    };
+    let Some(&(_, next)) = order.get(next) else {
+        return;
+    };
+
+    for (entity, _, mut camera_settings) in &mut cameras {
+        if entity == next {
+            camera_settings.smoothed_mouse_motion = Vec2::ZERO;
+        } else {
+            camera_settings.yaw_velocity = 0.0;
+            camera_settings.pitch_velocity = 0.0;
+            camera_settings.key_ramp = Vec2::ZERO;
+        }
+    }
+    commands.insert_resource(ActiveCamera(next));
+}
+
+/// Decide which cameras take input this frame. Only the [`ActiveCamera`] does, if there is one.
+/// Cameras with `viewport_input` do while the cursor is over their viewport, and keep or lose it
+/// for as long as a mouse button stays held, so a drag that crosses into another viewport stays
+/// with the camera it started on. That also keeps a drag begun before switching the active camera
+/// from carrying over to the new one.
+fn route_input(
+    mut cameras: Query<(Entity, &Camera, &mut CameraSettings), With<OrbitCamera>>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    active: Option<Res<ActiveCamera>>,
+) {
+    let mid_drag = mouse_buttons
+        .get_pressed()
+        .any(|button| !mouse_buttons.just_pressed(*button));
+    let cursor = window.cursor_position();
+    for (entity, camera, mut camera_settings) in &mut cameras {
+        let receives_input = if active.as_ref().is_some_and(|active| active.0 != entity) {
+            false
+        } else if mid_drag {
+            camera_settings.receives_input
+        } else {
+            !camera_settings.viewport_input
+                || cursor.is_some_and(|cursor| {
+                    camera
+                        .logical_viewport_rect()
+                        .is_some_and(|viewport| viewport.contains(cursor))
+                })
+        };
+        if camera_settings.receives_input != receives_input {
+            camera_settings.receives_input = receives_input;
+        }
+    }
+}
+
+/// Start the [`CameraRng`] over when the first orbit camera's `rng_seed` changes.
+fn seed_camera_rng(
+    cameras: Query<(Entity, &Camera, &CameraSettings), With<OrbitCamera>>,
+    mut rng: ResMut<CameraRng>,
+) {
+    let seed = cameras
+        .iter()
+        .min_by_key(|(entity, camera, _)| (camera.order, *entity))
+        .map(|(_, _, camera_settings)| camera_settings.rng_seed);
+    if let Some(seed) = seed.filter(|seed| *seed != rng.seed()) {
+        *rng = CameraRng::new(seed);
+    }
+}
+
+/// Drop mouse motion while the window is unfocused, and on the frame it regains focus.
+///
+/// Motion can pile up while the user is in another window, and applying it all at once on coming
+/// back would make the camera jump. Clearing it here covers every system that reads it.
+fn ignore_unfocused_motion(
+    window: Single<&Window, With<PrimaryWindow>>,
+    mut focus_events: EventReader<WindowFocused>,
+    mut mouse_motion: ResMut<AccumulatedMouseMotion>,
+) {
+    let regained_focus = focus_events.read().any(|event| event.focused);
+    if !window.focused || regained_focus {
+        mouse_motion.delta = Vec2::ZERO;
+    }
+}
+
+/// Take off the offsets `shake` and `sway` added last frame, so the other systems see the orbit
+/// pose.
+fn remove_shake(mut cameras: Query<(&mut Transform, &mut CameraSettings), With<OrbitCamera>>) {
+    for (mut camera, mut camera_settings) in &mut cameras {
+        // The sway went on after the shake, so it comes off first.
+        if let Some(rotation) = camera_settings.unswayed_rotation.take() {
+            camera.rotation = rotation;
+        }
+        if let Some(rotation) = camera_settings
+            .shake
+            .as_mut()
+            .and_then(|shake| shake.unshaken_rotation.take())
+        {
+            camera.rotation = rotation;
+        }
+        if camera_settings
+            .shake
+            .is_some_and(|shake| shake.elapsed >= shake.duration)
+        {
+            camera_settings.shake = None;
+        }
+    }
+}
+
+/// Turn the camera by a random offset that fades out over the shake's duration.
+///
+/// The orientation from before the offset is kept and put back by `remove_shake`, so the shake
+/// never feeds into the yaw, pitch and roll that the orbit controls work from.
+fn shake(
+    mut cameras: Query<(&mut Transform, &mut CameraSettings), With<OrbitCamera>>,
+    mut shakes: EventReader<CameraShake>,
+    time: Res<Time>,
+    mut rng: ResMut<CameraRng>,
+) {
+    let started = shakes.read().last().copied();
+
+    for (mut camera, mut camera_settings) in &mut cameras {
+        // `remove_shake` has already undone last frame's offset, so the shake can stop at once.
+        if camera_settings.reduce_motion {
+            if camera_settings.shake.is_some() {
+                camera_settings.shake = None;
+            }
+            continue;
+        }
+        if let Some(CameraShake {
+            amplitude,
+            duration,
+        }) = started
+        {
+            camera_settings.shake = Some(ActiveShake {
+                amplitude,
+                duration,
+                elapsed: 0.0,
+                unshaken_rotation: None,
+            });
+        }
+        let Some(shake) = &mut camera_settings.shake else {
+            continue;
+        };
+
+        shake.elapsed += time.delta_secs();
+        let remaining = if shake.duration > 0.0 {
+            (1.0 - shake.elapsed / shake.duration).max(0.0)
+        } else {
+            0.0
+        };
+        // Fading with the square of the remaining time makes the shake die away smoothly.
+        let strength = shake.amplitude * remaining * remaining;
+        if strength <= 0.0 {
+            continue;
+        }
+
+        let mut offset = || rng.gen_range(-1.0..=1.0) * strength;
+        let rotation = Quat::from_euler(EulerRot::YXZ, offset(), offset(), offset());
+        shake.unshaken_rotation = Some(camera.rotation);
+        camera.rotation *= rotation;
+    }
+}
+
+/// Drift the view by a small, slowly wandering yaw and pitch while the camera is left alone.
+///
+/// Each axis layers two sine waves at unrelated frequencies, with phases drawn from the
+/// [`CameraRng`], so the motion never visibly repeats yet plays out the same for the same seed.
+/// Like the shake, it is taken off again by `remove_shake` and never feeds into the orbit pose.
+fn sway(
+    mut cameras: Query<(&mut Transform, &mut CameraSettings), With<OrbitCamera>>,
+    mut rng: ResMut<CameraRng>,
+    time: Res<Time>,
+) {
+    // Seconds for the sway to fade in once idle, and out again on input.
+    const FADE_TIME: f32 = 0.5;
+    // Ratio between the frequencies of the two layers, chosen to avoid lining up.
+    const DETUNE: f32 = 2.31;
+
+    for (mut camera, mut camera_settings) in &mut cameras {
+        let enabled = camera_settings.sway_amplitude > 0.0 && !camera_settings.reduce_motion;
+        if !enabled {
+            if camera_settings.sway_phases.is_some() || camera_settings.sway_weight != 0.0 {
+                camera_settings.sway_phases = None;
+                camera_settings.sway_weight = 0.0;
+            }
+            continue;
+        }
+
+        let idle = camera_settings.idle_time > 0.0;
+        let blend = 1.0 - (-time.delta_secs() / FADE_TIME).exp();
+        let weight = camera_settings.sway_weight;
+        camera_settings.sway_weight = weight + (f32::from(idle) - weight) * blend;
+        let phases = *camera_settings
+            .sway_phases
+            .get_or_insert_with(|| std::array::from_fn(|_| rng.gen_range(0.0..TAU)));
+
+        let t = TAU * camera_settings.sway_frequency * time.elapsed_secs();
+        let wave = |phase: usize| {
+            ((t + phases[phase]).sin() + 0.5 * (t * DETUNE + phases[phase + 1]).sin()) / 1.5
+        };
+        let strength = camera_settings.sway_amplitude * camera_settings.sway_weight;
+        let (yaw, pitch) = (wave(0) * strength, wave(2) * strength);
+
+        camera_settings.unswayed_rotation = Some(camera.rotation);
+        camera.rotation =
+            (Quat::from_rotation_y(yaw) * camera.rotation * Quat::from_rotation_x(pitch))
+                .normalize();
+    }
+}
+
+/// Pull the camera in front of any geometry between it and the orbit target, so it never clips
+/// inside objects.
+///
+/// Only the camera's position is moved: `orbit_distance` keeps the desired distance. Moving in is
+/// instant, so the camera never ends up inside anything, while backing out once the obstruction
+/// clears is eased by a critically damped spring.
+fn avoid_collisions(
+    mut cameras: Query<(&mut Transform, &mut CameraSettings), With<OrbitCamera>>,
+    mut ray_cast: MeshRayCast,
+    time: Res<Time>,
+) {
+    // Once this close to the desired distance, the spring lets go.
+    const SETTLED_EPSILON: f32 = 1e-3;
+
+    let dt = time.delta_secs();
+    for (mut camera, mut camera_settings) in &mut cameras {
+        if camera_settings.mode != CameraMode::Orbit {
+            continue;
+        }
+        let target = camera_settings.orbit_target;
+        let ray = Ray3d::new(target, camera.back());
+        let allowed = ray_cast
+            .cast_ray(ray, &RayCastSettings::default())
+            .first()
+            .map(|(_, hit)| (hit.distance - camera_settings.collision_margin).max(0.0))
+            .filter(|&distance| distance < camera_settings.orbit_distance);
+
+        let distance = match (allowed, camera_settings.collision_distance) {
+            (None, None) => continue,
+            (Some(allowed), current) if current.is_none_or(|current| allowed <= current) => {
+                camera_settings.collision_velocity = 0.0;
+                allowed
+            }
+            (allowed, current) => {
+                let desired = allowed.unwrap_or(camera_settings.orbit_distance);
+                let current = current.unwrap_or(desired);
+                let omega = camera_settings.collision_spring_stiffness.sqrt();
+                let (distance, velocity) = critically_damped_step(
+                    current,
+                    camera_settings.collision_velocity,
+                    desired,
+                    omega,
+                    dt,
+                );
+                camera_settings.collision_velocity = velocity;
+                distance.min(desired)
+            }
+        };
+
+        camera_settings.collision_distance =
+            if allowed.is_none() && camera_settings.orbit_distance - distance < SETTLED_EPSILON {
+                None
+            } else {
+                Some(distance)
+            };
+        camera.translation = target + camera.back() * distance;
+    }
+}
+
+/// With `ground_margin` set, cast a ray down through each orbiting camera and lift it to that far
+/// above the first surface hit. Without a hit, like over the edge of the scene, the camera is left
+/// alone.
+///
+/// The ray starts level with the orbit target when the camera is lower, so a camera that has
+/// sunk into a hillside still finds the surface above it. Starting any higher would find roofs and
+/// overhangs the camera is meant to be under.
+fn follow_ground(
+    mut cameras: Query<(&mut Transform, &CameraSettings), With<OrbitCamera>>,
+    mut ray_cast: MeshRayCast,
+) {
+    for (mut camera, camera_settings) in &mut cameras {
+        if camera_settings.mode != CameraMode::Orbit {
+            continue;
+        }
+        let Some(margin) = camera_settings.ground_margin else {
+            continue;
+        };
+        let target = camera_settings.orbit_target;
+        let mut origin = camera.translation;
+        origin.y = origin.y.max(target.y);
+        let ray = Ray3d::new(origin, Dir3::NEG_Y);
+        let Some((_, hit)) = ray_cast.cast_ray(ray, &RayCastSettings::default()).first() else {
+            continue;
+        };
+        let floor = hit.point.y + margin;
+        if camera.translation.y < floor {
+            raise_to_floor(&mut camera, target, floor);
+        }
+    }
+}
+
+/// With `keep_target_onscreen` set, turn each camera a little toward its orbit target while the
+/// target is off screen or within `onscreen_margin` of the edge of the view. The turn eases off
+/// exponentially, the same at any frame rate, and stops once the target is back inside.
+///
+/// The camera is turned rather than the target moved, so the target stays on whatever it was put
+/// on. Only orbit cameras are turned: in first person the camera stands at the target, so there is
+/// nothing to keep in view, and flying the camera would have it fight the mouse look.
+fn keep_target_onscreen(
+    mut cameras: Query<(&mut Transform, &CameraSettings, &Camera), With<OrbitCamera>>,
+    time: Res<Time>,
+) {
+    let dt = time.delta_secs();
+    for (mut transform, camera_settings, camera) in &mut cameras {
+        if !camera_settings.keep_target_onscreen || camera_settings.mode != CameraMode::Orbit {
+            continue;
+        }
+        let Some(viewport_size) = camera.logical_viewport_size() else {
+            continue;
+        };
+        let target = camera_settings.orbit_target;
+        if transform.translation.distance_squared(target) <= f32::EPSILON {
+            continue;
+        }
+
+        // `GlobalTransform` is a frame behind, so project with where the camera is now. A target
+        // behind the camera has no position on screen, and needs turning toward too.
+        let margin = viewport_size * camera_settings.onscreen_margin.clamp(0.0, 0.5);
+        let inside = Rect::from_corners(margin, viewport_size - margin);
+        let onscreen = camera
+            .world_to_viewport(&GlobalTransform::from(*transform), target)
+            .is_ok_and(|position| inside.contains(position));
+        if onscreen {
+            continue;
+        }
+
+        let blend = if camera_settings.onscreen_smooth_time > 0.0 {
+            1.0 - (-dt / camera_settings.onscreen_smooth_time).exp()
+        } else {
+            1.0
+        };
+        let up = transform.up();
+        let facing_target = transform.looking_at(target, up).rotation;
+        transform.rotation = transform.rotation.slerp(facing_target, blend);
+    }
+}
+
+/// Advance a critically damped spring with natural frequency `omega` by `dt`, returning the new
+/// position and velocity. The closed-form solution stays stable at any frame rate.
+fn critically_damped_step(
+    position: f32,
+    velocity: f32,
+    target: f32,
+    omega: f32,
+    dt: f32,
+) -> (f32, f32) {
+    let offset = position - target;
+    let decay = (-omega * dt).exp();
+    let temp = (velocity + omega * offset) * dt;
+    let velocity = (velocity - omega * temp) * decay;
+    let offset = (offset + temp) * decay;
+    (target + offset, velocity)
+}
+
+/// Move `current` towards `target` at a constant rate, taking `attack_time` seconds to go from 0
+/// to full magnitude and `release_time` to come back. Either at 0 jumps straight there.
+fn ramp_toward(current: f32, target: f32, attack_time: f32, release_time: f32, dt: f32) -> f32 {
+    let rising = target.abs() > current.abs() && current * target >= 0.0;
+    let time = if rising { attack_time } else { release_time };
+    if time <= 0.0 {
+        return target;
+    }
+    let step = dt / time;
+    let remaining = target - current;
+    if remaining.abs() <= step {
+        target
+    } else {
+        current + remaining.signum() * step
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::{
+        ecs::system::{RunSystemOnce, SystemState},
+        gizmos::LineGizmo,
+        input::mouse::{
+            accumulate_mouse_motion_system, accumulate_mouse_scroll_system, MouseMotion, MouseWheel,
+        },
+        render::camera::{camera_system, CameraProjection, ManualTextureViews, Viewport},
+        time::TimeUpdateStrategy,
+        window::{WindowCreated, WindowResized, WindowScaleFactorChanged},
+    };
+    use std::time::Duration;
+
+    /// How far each update of a test app advances time, so runs don't depend on the machine.
+    const FRAME: Duration = Duration::from_millis(16);
+
+    /// A headless app that runs `orbit` on its own, fed by input the test sets up by hand.
+    ///
+    /// Mouse motion and scroll go through the same events and accumulating systems as in a real
+    /// app. `InputPlugin` is left out, so held buttons stay held until the test releases them.
+    fn orbit_app() -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(FRAME))
+            .init_resource::<KeyBindings>()
+            .init_resource::<ButtonInput<MouseButton>>()
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<AccumulatedMouseMotion>()
+            .init_resource::<AccumulatedMouseScroll>()
+            .add_event::<MouseMotion>()
+            .add_event::<MouseWheel>()
+            .add_event::<CameraMoved>()
+            .add_systems(
+                PreUpdate,
+                (
+                    accumulate_mouse_motion_system,
+                    accumulate_mouse_scroll_system,
+                ),
+            )
+            .add_systems(Update, orbit);
+        // The first update only starts the clock.
+        app.update();
+        app
+    }
+
+    /// Spawn an orbit camera placed where `settings` says it starts.
+    fn spawn_camera(app: &mut App, settings: CameraSettings) -> Entity {
+        let transform = initial_camera_transform(&settings);
+        app.world_mut()
+            .spawn((OrbitCamera, settings, transform))
+            .id()
+    }
+
+    /// Move the mouse by `delta` over the next update.
+    fn move_mouse(app: &mut App, delta: Vec2) {
+        app.world_mut().send_event(MouseMotion { delta });
+    }
+
+    fn transform(app: &App, camera: Entity) -> Transform {
+        *app.world().get::<Transform>(camera).unwrap()
+    }
+
+    fn settings(app: &App, camera: Entity) -> &CameraSettings {
+        app.world().get::<CameraSettings>(camera).unwrap()
+    }
+
+    /// Settings that apply input at once, without easing or momentum, so one update shows all of
+    /// its effect.
+    fn immediate_settings() -> CameraSettings {
+        CameraSettings {
+            rotate_smoothing: 0.0,
+            zoom_smoothing: 0.0,
+            pan_smoothing: 0.0,
+            enable_inertia: false,
+            ..default()
+        }
+    }
+
+    #[test]
+    fn dragging_orbits_the_camera() {
+        let mut app = orbit_app();
+        let camera = spawn_camera(&mut app, immediate_settings());
+        let before = transform(&app, camera);
+
+        app.world_mut()
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .press(MouseButton::Left);
+        move_mouse(&mut app, Vec2::new(50.0, 20.0));
+        app.update();
+
+        let after = transform(&app, camera);
+        let (yaw_before, pitch_before, _) = before.rotation.to_euler(EulerRot::YXZ);
+        let (yaw_after, pitch_after, _) = after.rotation.to_euler(EulerRot::YXZ);
+        assert!((yaw_after - yaw_before).abs() > 0.1, "yaw didn't change");
+        assert!(
+            (pitch_after - pitch_before).abs() > 0.01,
+            "pitch didn't change"
+        );
+        assert!(after.translation.distance(before.translation) > 0.1);
+
+        // The camera still circles the target, looking at it from the same distance.
+        let target = settings(&app, camera).orbit_target;
+        assert!((after.translation.distance(target) - 20.0).abs() < 1e-3);
+        assert!(
+            after
+                .forward()
+                .dot((target - after.translation).normalize())
+                > 0.9999
+        );
+    }
+
+    #[test]
+    fn camera_holds_still_without_input() {
+        let mut app = orbit_app();
+        let camera = spawn_camera(&mut app, immediate_settings());
+        let before = transform(&app, camera);
+
+        for _ in 0..3 {
+            app.update();
+        }
+
+        let after = transform(&app, camera);
+        assert!(after.translation.abs_diff_eq(before.translation, 1e-4));
+        assert!(after.rotation.abs_diff_eq(before.rotation, 1e-5));
+    }
+
+    #[test]
+    fn distance_is_clamped_to_distance_range() {
+        let mut settings = CameraSettings::default();
+        settings.set_distance(1000.0);
+        assert_eq!(settings.target_distance, settings.distance_range.end);
+        settings.set_distance(0.0);
+        assert_eq!(settings.target_distance, settings.distance_range.start);
+        settings.set_distance(10.0);
+        assert_eq!(settings.target_distance, 10.0);
+    }
+
+    #[test]
+    fn scrolling_stops_at_both_ends_of_distance_range() {
+        let mut app = orbit_app();
+        app.add_systems(Update, zoom.before(orbit));
+        let camera = spawn_camera(&mut app, immediate_settings());
+
+        for (notches, end) in [(-1000.0, 80.0), (1000.0, 2.0)] {
+            app.world_mut().send_event(MouseWheel {
+                unit: MouseScrollUnit::Line,
+                x: 0.0,
+                y: notches,
+                window: Entity::PLACEHOLDER,
+            });
+            app.update();
+            app.update();
+            let settings = settings(&app, camera);
+            assert_eq!(settings.target_distance, end);
+            assert!((settings.orbit_distance - end).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn panning_stops_at_target_bounds() {
+        let mut app = orbit_app();
+        app.add_systems(Update, pan.before(orbit));
+        let bounds = (Vec3::splat(-3.0), Vec3::splat(3.0));
+        let camera = spawn_camera(
+            &mut app,
+            CameraSettings {
+                target_bounds: Some(bounds),
+                ..immediate_settings()
+            },
+        );
+        app.world_mut()
+            .entity_mut(camera)
+            .insert((Camera::default(), Projection::default()));
+
+        // Long enough to carry an unbounded target well past the edge.
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::ArrowRight);
+        for _ in 0..120 {
+            app.update();
+        }
+
+        let target = settings(&app, camera).orbit_target;
+        assert!(target.cmpge(bounds.0).all() && target.cmple(bounds.1).all());
+        // Heading right from the default view runs into at least one face of the box.
+        assert!(target.abs().max_element() > 3.0 - 1e-4);
+    }
+
+    #[test]
+    fn applying_tuning_keeps_settings_the_file_leaves_out() {
+        let mut camera_settings = CameraSettings {
+            enable_turntable: true,
+            min_camera_height: Some(0.1),
+            ..default()
+        };
+        let tuning = CameraSettings {
+            yaw_speed: 0.5,
+            ..default()
+        };
+
+        camera_settings.apply_tuning(&tuning, &["yaw_speed".to_string()]);
+
+        assert_eq!(camera_settings.yaw_speed, 0.5);
+        assert!(camera_settings.enable_turntable);
+        assert_eq!(camera_settings.min_camera_height, Some(0.1));
+    }
+
+    #[test]
+    fn applying_tuning_keeps_each_cameras_setup() {
+        let mut camera_settings = CameraSettings {
+            initial_yaw: 1.0,
+            viewport_input: true,
+            ..default()
+        };
+        let tuning = CameraSettings {
+            initial_yaw: 0.0,
+            viewport_input: false,
+            ..default()
+        };
+
+        let fields = ["initial_yaw".to_string(), "viewport_input".to_string()];
+        camera_settings.apply_tuning(&tuning, &fields);
+
+        assert_eq!(camera_settings.initial_yaw, 1.0);
+        assert!(camera_settings.viewport_input);
+        let ignored: Vec<_> = CameraSettings::per_camera_fields(&fields).collect();
+        assert_eq!(ignored, ["initial_yaw", "viewport_input"]);
+    }
+
+    /// Drag across one update with the left button and return how far that turned the camera,
+    /// as (yaw, pitch).
+    fn drag_turn(settings: CameraSettings, delta: Vec2) -> (f32, f32) {
+        let mut app = orbit_app();
+        let camera = spawn_camera(&mut app, settings);
+        let before = transform(&app, camera).rotation.to_euler(EulerRot::YXZ);
+
+        app.world_mut()
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .press(MouseButton::Left);
+        move_mouse(&mut app, delta);
+        app.update();
+
+        let after = transform(&app, camera).rotation.to_euler(EulerRot::YXZ);
+        (after.0 - before.0, after.1 - before.1)
+    }
+
+    #[test]
+    fn inverting_flips_the_turn_from_a_drag() {
+        let delta = Vec2::new(40.0, 30.0);
+        let (yaw, pitch) = drag_turn(immediate_settings(), delta);
+        let (inverted_yaw, inverted_pitch) = drag_turn(
+            CameraSettings {
+                invert_pitch: true,
+                invert_yaw: true,
+                ..immediate_settings()
+            },
+            delta,
+        );
+
+        assert!(yaw.abs() > 0.05 && pitch.abs() > 0.05);
+        assert!((inverted_yaw + yaw).abs() < 1e-3, "{inverted_yaw} vs {yaw}");
+        assert!(
+            (inverted_pitch + pitch).abs() < 1e-3,
+            "{inverted_pitch} vs {pitch}"
+        );
+    }
+
+    #[test]
+    fn next_orbit_transform_keeps_pitch_in_range() {
+        let settings = CameraSettings::default();
+        let current = initial_camera_transform(&settings);
+
+        for delta_pitch in [10.0, -10.0] {
+            let next = next_orbit_transform(
+                &current,
+                &settings,
+                OrbitInput {
+                    delta_pitch,
+                    ..default()
+                },
+            );
+            let pitch = next.forward().y.asin();
+            let limit = if delta_pitch > 0.0 {
+                settings.pitch_range.end
+            } else {
+                settings.pitch_range.start
+            };
+            assert!((pitch - limit).abs() < 1e-4, "{pitch} vs {limit}");
+        }
+    }
+
+    #[test]
+    fn next_orbit_transform_places_the_camera_around_the_target() {
+        let settings = CameraSettings {
+            orbit_target: Vec3::new(1.0, 2.0, -3.0),
+            orbit_distance: 7.0,
+            ..default()
+        };
+        let current = initial_camera_transform(&settings);
+
+        let next = next_orbit_transform(
+            &current,
+            &settings,
+            OrbitInput {
+                delta_yaw: 0.5,
+                delta_pitch: 0.2,
+                delta_distance: 1.0,
+                ..default()
+            },
+        );
+
+        let target = settings.orbit_target;
+        assert!((next.translation.distance(target) - 8.0).abs() < 1e-4);
+        assert!(next
+            .translation
+            .abs_diff_eq(target - next.forward() * 8.0, 1e-4));
+    }
+
+    #[test]
+    fn next_orbit_transform_without_input_leaves_the_camera_alone() {
+        let settings = CameraSettings::default();
+        let current = initial_camera_transform(&settings);
+
+        let next = next_orbit_transform(&current, &settings, OrbitInput::default());
+
+        assert!(next.translation.abs_diff_eq(current.translation, 1e-4));
+        assert!(next.rotation.abs_diff_eq(current.rotation, 1e-6));
+    }
+
+    /// A headless app that only layers camera shake on, drawing from a [`CameraRng`] seeded with
+    /// `seed`.
+    fn shake_app(seed: u64) -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(FRAME))
+            .insert_resource(CameraRng::new(seed))
+            .add_event::<CameraShake>()
+            .add_systems(Update, (remove_shake, shake).chain());
+        app.update();
+        app
+    }
+
+    /// Shake the camera for `frames` updates, starting on the first, and return its rotation after
+    /// each one.
+    fn shake_rotations(app: &mut App, camera: Entity, frames: usize) -> Vec<Quat> {
+        app.world_mut().send_event(CameraShake {
+            amplitude: 0.2,
+            duration: 0.25,
+        });
+        (0..frames)
+            .map(|_| {
+                app.update();
+                transform(app, camera).rotation
+            })
+            .collect()
+    }
+
+    #[test]
+    fn shake_settles_back_on_the_exact_orientation() {
+        let mut app = shake_app(7);
+        let camera = spawn_camera(&mut app, CameraSettings::default());
+        let before = transform(&app, camera);
+
+        let rotations = shake_rotations(&mut app, camera, 30);
+
+        assert!(rotations[0].angle_between(before.rotation) > 1e-3);
+        assert_eq!(*rotations.last().unwrap(), before.rotation);
+        assert_eq!(transform(&app, camera).translation, before.translation);
+        assert!(settings(&app, camera).shake.is_none());
+    }
+
+    #[test]
+    fn rotation_speeds_follow_the_distance() {
+        let at = |orbit_distance| CameraSettings {
+            sensitivity_scales_with_distance: true,
+            sensitivity_reference_distance: 20.0,
+            pitch_distance_exponent: 0.5,
+            yaw_distance_exponent: 1.0,
+            orbit_distance,
+            ..default()
+        };
+        let (pitch_speed, yaw_speed) = (at(20.0).pitch_speed, at(20.0).yaw_speed);
+
+        assert_eq!(at(20.0).rotation_speeds(), (pitch_speed, yaw_speed));
+        let (pitch, yaw) = at(5.0).rotation_speeds();
+        assert!((pitch - pitch_speed * 0.5).abs() < 1e-7);
+        assert!((yaw - yaw_speed * 0.25).abs() < 1e-7);
+
+        // With the default square root scaling, a drag at a quarter of the distance turns the
+        // camera half as far.
+        let delta = Vec2::new(20.0, 0.0);
+        let (far, _) = drag_turn(
+            CameraSettings {
+                sensitivity_scales_with_distance: true,
+                ..immediate_settings()
+            },
+            delta,
+        );
+        let (near, _) = drag_turn(
+            CameraSettings {
+                sensitivity_scales_with_distance: true,
+                orbit_distance: 5.0,
+                target_distance: 5.0,
+                ..immediate_settings()
+            },
+            delta,
+        );
+        assert!((near / far - 0.5).abs() < 1e-3, "{near} vs {far}");
+    }
+
+    #[test]
+    fn auto_level_roll_settles_on_level() {
+        let mut app = orbit_app();
+        let camera = spawn_camera(
+            &mut app,
+            CameraSettings {
+                auto_level_roll: true,
+                initial_roll: 0.6,
+                ..immediate_settings()
+            },
+        );
+        let roll = |app: &App| roll_angle(transform(app, camera).rotation, false).unwrap();
+        assert!((roll(&app) - 0.6).abs() < 1e-4);
+
+        // Three seconds at the default rate leaves a ten thousandth of the roll.
+        for _ in 0..190 {
+            app.update();
+        }
+
+        assert!(roll(&app).abs() < 1e-3, "roll is still {}", roll(&app));
+    }
+
+    #[test]
+    fn smooth_damp_arrives_without_overshooting() {
+        for (current, target) in [(0.0, 10.0), (10.0, -5.0)] {
+            let mut value = current;
+            let mut velocity = 0.0;
+            for _ in 0..200 {
+                value = smooth_damp(value, target, &mut velocity, 0.3, 1.0 / 60.0);
+                assert!(
+                    (value - target) * (current - target) >= 0.0,
+                    "passed {target}"
+                );
+            }
+            assert!((value - target).abs() < 1e-3);
+        }
+
+        // Momentum left over from a faraway target stops at a close one instead of passing it.
+        let mut velocity = 50.0;
+        assert_eq!(smooth_damp(9.9, 10.0, &mut velocity, 0.3, 0.1), 10.0);
+        assert_eq!(velocity, 0.0);
+    }
+
+    #[test]
+    fn smooth_damp_is_frame_rate_independent() {
+        let run = |steps: usize| {
+            let (mut value, mut velocity) = (0.0, 0.0);
+            for _ in 0..steps {
+                value = smooth_damp(value, 10.0, &mut velocity, 0.5, 0.5 / steps as f32);
+            }
+            value
+        };
+        assert!((run(10) - run(120)).abs() < 1e-3);
+    }
+
+    /// Whether `a` and `b` are the same heading, to within `1e-5`.
+    fn same_yaw(a: f32, b: f32) -> bool {
+        ((a - b + PI).rem_euclid(TAU) - PI).abs() < 1e-5
+    }
+
+    #[test]
+    fn clamp_yaw_limits_to_the_front_half() {
+        let range = -FRAC_PI_2..FRAC_PI_2;
+        assert!(same_yaw(clamp_yaw(0.5, &range), 0.5));
+        assert!(same_yaw(clamp_yaw(-1.5, &range), -1.5));
+        assert!(same_yaw(clamp_yaw(2.0, &range), FRAC_PI_2));
+        assert!(same_yaw(clamp_yaw(-2.0, &range), -FRAC_PI_2));
+        // Just either side of straight behind goes to the nearer end, never across the range.
+        assert!(same_yaw(clamp_yaw(PI - 0.1, &range), FRAC_PI_2));
+        assert!(same_yaw(clamp_yaw(-PI + 0.1, &range), -FRAC_PI_2));
+        // A whole turn round is the same heading.
+        assert!(same_yaw(clamp_yaw(0.5 + TAU, &range), 0.5));
+    }
+
+    #[test]
+    fn clamp_yaw_wraps_an_end_below_the_start() {
+        // 300°..60°, the 120° through 0.
+        let range = 300f32.to_radians()..60f32.to_radians();
+        assert!(same_yaw(clamp_yaw(0.0, &range), 0.0));
+        assert!(same_yaw(clamp_yaw(-0.5, &range), -0.5));
+        assert!(same_yaw(clamp_yaw(2.5, &range), PI / 3.0));
+        assert!(same_yaw(clamp_yaw(-FRAC_PI_2, &range), -PI / 3.0));
+    }
+
+    #[test]
+    fn dragging_stops_at_the_end_of_yaw_range() {
+        let mut app = orbit_app();
+        let camera = spawn_camera(
+            &mut app,
+            CameraSettings {
+                yaw_range: Some(-FRAC_PI_2..FRAC_PI_2),
+                initial_yaw: 0.0,
+                ..immediate_settings()
+            },
+        );
+        app.world_mut()
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .press(MouseButton::Left);
+        for _ in 0..10 {
+            move_mouse(&mut app, Vec2::new(-200.0, 0.0));
+            app.update();
+        }
+
+        let (yaw, _, _) = transform(&app, camera).rotation.to_euler(EulerRot::YXZ);
+        assert!((yaw.abs() - FRAC_PI_2).abs() < 1e-3, "yaw is {yaw}");
+    }
+
+    /// Moves its entity at this velocity, in units per second.
+    #[derive(Component)]
+    struct Slide(Vec3);
+
+    fn slide(mut sliding: Query<(&mut Transform, &Slide)>, time: Res<Time>) {
+        for (mut transform, Slide(velocity)) in &mut sliding {
+            transform.translation += velocity * time.delta_secs();
+        }
+    }
+
+    /// An orbit app with a camera following an entity that `slide` moves before `orbit` runs.
+    fn follow_app(follow_smooth_time: f32, velocity: Vec3) -> (App, Entity, Entity) {
+        let mut app = orbit_app();
+        app.add_systems(Update, slide.before(orbit));
+        let followed = app
+            .world_mut()
+            .spawn((Transform::default(), Slide(velocity)))
+            .id();
+        let camera = spawn_camera(
+            &mut app,
+            CameraSettings {
+                follow_target: Some(followed),
+                follow_smooth_time,
+                ..immediate_settings()
+            },
+        );
+        (app, camera, followed)
+    }
+
+    #[test]
+    fn follow_spring_catches_up_without_overshooting() {
+        let (mut app, camera, followed) = follow_app(0.3, Vec3::new(5.0, 0.0, 0.0));
+
+        // About a second along a straight line, then a stop.
+        for frame in 0..250 {
+            if frame == 60 {
+                app.world_mut().get_mut::<Slide>(followed).unwrap().0 = Vec3::ZERO;
+            }
+            app.update();
+            let position = transform(&app, followed).translation;
+            let pivot = settings(&app, camera).orbit_target;
+            assert!(pivot.x <= position.x + 1e-5, "passed the target on {frame}");
+            assert!(pivot.yz().abs_diff_eq(Vec2::ZERO, 1e-5));
+            if frame == 59 {
+                assert!(position.x - pivot.x > 0.5, "didn't lag behind");
+            }
+        }
+
+        let position = transform(&app, followed).translation;
+        assert!(settings(&app, camera)
+            .orbit_target
+            .abs_diff_eq(position, 1e-3));
+    }
+
+    const EASINGS: [Easing; 6] = [
+        Easing::Linear,
+        Easing::EaseIn,
+        Easing::EaseOut,
+        Easing::EaseInOut,
+        Easing::Elastic,
+        Easing::Smoothstep,
+    ];
+
+    #[test]
+    fn every_easing_starts_at_0_and_ends_at_1() {
+        for easing in EASINGS {
+            assert_eq!(ease(easing, 0.0), 0.0, "{easing:?}");
+            assert_eq!(ease(easing, 1.0), 1.0, "{easing:?}");
+            // Progress outside the animation is held at its ends.
+            assert_eq!(ease(easing, -0.5), 0.0, "{easing:?}");
+            assert_eq!(ease(easing, 1.5), 1.0, "{easing:?}");
+        }
+    }
+
+    #[test]
+    fn elastic_easing_overshoots_and_settles() {
+        let samples: Vec<f32> = (0..=100)
+            .map(|i| ease(Easing::Elastic, i as f32 / 100.0))
+            .collect();
+        assert!(samples.iter().any(|value| *value > 1.0));
+        assert!((samples[99] - 1.0).abs() < 1e-2);
+        // Only Elastic leaves the 0 to 1 range.
+        for easing in EASINGS
+            .into_iter()
+            .filter(|easing| *easing != Easing::Elastic)
+        {
+            for i in 0..=100 {
+                let value = ease(easing, i as f32 / 100.0);
+                assert!((0.0..=1.0).contains(&value), "{easing:?}");
+            }
+        }
+    }
+
+    /// The heading the camera looks along, in the same terms as `yaw_range`.
+    fn heading(transform: &Transform) -> f32 {
+        let forward = transform.forward();
+        f32::atan2(-forward.x, -forward.z)
+    }
+
+    #[test]
+    fn snapping_clicks_yaw_between_steps() {
+        let mut app = orbit_app();
+        let camera = spawn_camera(
+            &mut app,
+            CameraSettings {
+                initial_yaw: 0.0,
+                ..immediate_settings()
+            },
+        );
+        let step = settings(&app, camera).snap_angle;
+        let yaw_speed = settings(&app, camera).yaw_speed;
+        app.world_mut()
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .press(MouseButton::Left);
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::ControlLeft);
+
+        // Less than half a step stays put.
+        move_mouse(&mut app, Vec2::new(0.4 * step / yaw_speed, 0.0));
+        app.update();
+        assert!(same_yaw(heading(&transform(&app, camera)), 0.0));
+
+        // Carrying on past half a step clicks over to the next one.
+        move_mouse(&mut app, Vec2::new(0.2 * step / yaw_speed, 0.0));
+        app.update();
+        let yaw = heading(&transform(&app, camera));
+        assert!(same_yaw(yaw.abs(), step), "yaw is {yaw}");
+
+        // Letting go of the modifier turns freely again.
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .release(KeyCode::ControlLeft);
+        move_mouse(&mut app, Vec2::new(0.1 * step / yaw_speed, 0.0));
+        app.update();
+        let free = heading(&transform(&app, camera));
+        assert!((free.abs() - 1.1 * step).abs() < 1e-3, "yaw is {free}");
+    }
+
+    /// An orthographic projection showing `width` by `height` world units, laid out for a
+    /// `viewport` of that size.
+    fn fixed_orthographic(width: f32, height: f32, viewport: Vec2) -> OrthographicProjection {
+        let mut orthographic = OrthographicProjection {
+            scaling_mode: ScalingMode::Fixed { width, height },
+            ..OrthographicProjection::default_3d()
+        };
+        orthographic.update(viewport.x, viewport.y);
+        orthographic
+    }
+
+    #[test]
+    fn perspective_pixels_grow_with_distance() {
+        let projection = Projection::Perspective(PerspectiveProjection {
+            fov: FRAC_PI_2,
+            ..default()
+        });
+        let viewport = Vec2::new(800.0, 500.0);
+
+        // The view is 2 * distance tall, over 500 pixels.
+        let near = world_per_pixel(&projection, 10.0, viewport);
+        assert!(near.abs_diff_eq(Vec2::splat(0.04), 1e-6), "{near}");
+        let far = world_per_pixel(&projection, 20.0, viewport);
+        assert!(far.abs_diff_eq(near * 2.0, 1e-6), "{far}");
+    }
+
+    #[test]
+    fn orthographic_pixels_follow_the_fixed_area() {
+        let viewport = Vec2::new(800.0, 400.0);
+        let projection = Projection::Orthographic(fixed_orthographic(8.0, 8.0, viewport));
+
+        // Fixed stretches 8 by 8 over the window, whatever the distance.
+        for distance in [2.0, 20.0] {
+            let size = world_per_pixel(&projection, distance, viewport);
+            assert!(size.abs_diff_eq(Vec2::new(0.01, 0.02), 1e-6), "{size}");
+        }
+        assert_eq!(world_per_pixel(&projection, 2.0, Vec2::ZERO), Vec2::ZERO);
+    }
+
+    #[test]
+    fn followed_pivot_is_where_the_target_is_this_frame() {
+        let (mut app, camera, followed) = follow_app(0.0, Vec3::new(3.0, 0.0, -2.0));
+
+        for _ in 0..5 {
+            app.update();
+            let position = transform(&app, followed).translation;
+            assert_eq!(settings(&app, camera).orbit_target, position);
+            let camera_transform = transform(&app, camera);
+            let to_target = (position - camera_transform.translation).normalize();
+            assert!(camera_transform.forward().dot(to_target) > 0.9999);
+        }
+    }
+
+    #[test]
+    fn fit_distance_for_entity_frames_each_cube() {
+        let mut world = World::new();
+        let aabb = Cuboid::default().mesh().build().compute_aabb().unwrap();
+        // Where the example puts its cubes, with the spinning one caught part way round.
+        let cubes = [
+            Transform::from_xyz(1.5, 0.51, 1.5),
+            Transform::from_xyz(-2.5, 0.51, 2.5),
+            Transform::from_xyz(1.5, 0.51, -1.5),
+            Transform::from_xyz(-1.5, 0.51, -1.5).with_rotation(Quat::from_rotation_y(0.7)),
+        ]
+        .map(|transform| world.spawn((GlobalTransform::from(transform), aabb)).id());
+        let unmeasured = world.spawn(GlobalTransform::from_xyz(0.0, 2.0, 0.0)).id();
+        let unplaced = world.spawn_empty().id();
+
+        let mut state: SystemState<(Query<&GlobalTransform>, Query<&Aabb>)> =
+            SystemState::new(&mut world);
+        let (transforms, aabbs) = state.get(&world);
+        let projection = Projection::default();
+        let fit = |entity| fit_distance_for_entity(entity, &transforms, &aabbs, &projection);
+
+        // A unit cube reaches sqrt(3) / 2 from its center, whichever way it is turned, and gets a
+        // tenth more room.
+        let cube_distance = fit_distance_for_bounds(3f32.sqrt() / 2.0 * 1.1, &projection);
+        for (cube, position) in cubes.iter().zip([
+            Vec3::new(1.5, 0.51, 1.5),
+            Vec3::new(-2.5, 0.51, 2.5),
+            Vec3::new(1.5, 0.51, -1.5),
+            Vec3::new(-1.5, 0.51, -1.5),
+        ]) {
+            let (center, distance) = fit(*cube).unwrap();
+            assert!(center.abs_diff_eq(position, 1e-5), "{center}");
+            assert!((distance - cube_distance).abs() < 1e-4, "{distance}");
+        }
+
+        // Without an Aabb, a unit sphere around the position.
+        let (center, distance) = fit(unmeasured).unwrap();
+        assert_eq!(center, Vec3::new(0.0, 2.0, 0.0));
+        assert!((distance - fit_distance_for_bounds(1.1, &projection)).abs() < 1e-4);
+
+        assert!(fit(unplaced).is_none());
+    }
+
+    #[test]
+    fn same_seed_shakes_the_same_way() {
+        let run = |seed| {
+            let mut app = shake_app(seed);
+            let camera = spawn_camera(&mut app, CameraSettings::default());
+            shake_rotations(&mut app, camera, 10)
+        };
+
+        assert_eq!(run(42), run(42));
+        assert_ne!(run(42), run(43));
+    }
+
+    #[test]
+    fn screen_speed_scale_ignores_distance_in_orthographic() {
+        let at = |orbit_distance| CameraSettings {
+            constant_screen_speed: true,
+            orbit_distance,
+            ..default()
+        };
+        let orthographic =
+            Projection::Orthographic(fixed_orthographic(8.0, 8.0, Vec2::new(800.0, 600.0)));
+        let near = at(5.0).screen_speed_scale(&orthographic);
+        assert!(near > 0.0);
+        assert_eq!(at(40.0).screen_speed_scale(&orthographic), near);
+
+        // Perspective speeds up in step with distance, from 1 at the reference distance.
+        let perspective = Projection::default();
+        let reference = at(20.0).sensitivity_reference_distance;
+        assert!((at(reference).screen_speed_scale(&perspective) - 1.0).abs() < 1e-6);
+        let ratio =
+            at(40.0).screen_speed_scale(&perspective) / at(10.0).screen_speed_scale(&perspective);
+        assert!((ratio - 4.0).abs() < 1e-5);
+
+        // Off, the speeds are left alone.
+        let off = CameraSettings {
+            orbit_distance: 5.0,
+            ..default()
+        };
+        assert_eq!(off.screen_speed_scale(&orthographic), 1.0);
+        assert_eq!(
+            off.pointer_rotation_speeds(Some(&orthographic)),
+            off.rotation_speeds()
+        );
+    }
+
+    /// Turn, zoom and pan the camera over a single update, then let go. Returns the heading,
+    /// distance and orbit target right after that update and once everything has settled.
+    fn turn_zoom_and_pan(camera_settings: CameraSettings) -> [(f32, f32, Vec3); 2] {
+        let mut app = orbit_app();
+        app.add_systems(Update, (zoom, pan).before(orbit));
+        let camera = spawn_camera(&mut app, camera_settings);
+        app.world_mut()
+            .entity_mut(camera)
+            .insert((Camera::default(), Projection::default()));
+        let pose = |app: &App| {
+            let settings = settings(app, camera);
+            (
+                heading(&transform(app, camera)),
+                settings.orbit_distance,
+                settings.orbit_target,
+            )
+        };
+
+        app.world_mut()
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .press(MouseButton::Left);
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::ArrowRight);
+        move_mouse(&mut app, Vec2::new(50.0, 0.0));
+        app.world_mut().send_event(MouseWheel {
+            unit: MouseScrollUnit::Line,
+            x: 0.0,
+            y: 2.0,
+            window: Entity::PLACEHOLDER,
+        });
+        app.update();
+        let moved = pose(&app);
+
+        app.world_mut()
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .release(MouseButton::Left);
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .release(KeyCode::ArrowRight);
+        for _ in 0..300 {
+            app.update();
+        }
+        [moved, pose(&app)]
+    }
+
+    #[test]
+    fn each_smoothing_only_eases_its_own_input() {
+        let [immediate, settled] = turn_zoom_and_pan(immediate_settings());
+        let start = CameraSettings::default();
+        assert!(!same_yaw(immediate.0, start.initial_yaw));
+        assert!(immediate.1 < start.orbit_distance);
+        assert_ne!(immediate.2, start.orbit_target);
+
+        // Smoothing one input holds that one back at first, leaving the other two as they were.
+        let smoothed = |settings: CameraSettings| turn_zoom_and_pan(settings)[0];
+        let (yaw, distance, target) = smoothed(CameraSettings {
+            rotate_smoothing: 0.3,
+            ..immediate_settings()
+        });
+        assert!(!same_yaw(yaw, immediate.0));
+        assert_eq!((distance, target), (immediate.1, immediate.2));
+
+        let (yaw, distance, target) = smoothed(CameraSettings {
+            zoom_smoothing: 0.3,
+            ..immediate_settings()
+        });
+        assert!((distance - immediate.1).abs() > 0.1);
+        assert!(same_yaw(yaw, immediate.0));
+        assert_eq!(target, immediate.2);
+
+        let (yaw, distance, target) = smoothed(CameraSettings {
+            pan_smoothing: 0.3,
+            ..immediate_settings()
+        });
+        assert!(!target.abs_diff_eq(immediate.2, 1e-4));
+        assert!(same_yaw(yaw, immediate.0));
+        assert_eq!(distance, immediate.1);
+
+        // With all three on, everything still ends up exactly where it would without smoothing.
+        let [_, all] = turn_zoom_and_pan(CameraSettings {
+            rotate_smoothing: 0.3,
+            zoom_smoothing: 0.3,
+            pan_smoothing: 0.3,
+            ..immediate_settings()
+        });
+        assert!(
+            (all.0 - settled.0).abs() < 1e-4,
+            "{} vs {}",
+            all.0,
+            settled.0
+        );
+        assert!((all.1 - settled.1).abs() < 1e-4);
+        assert!(all.2.abs_diff_eq(settled.2, 1e-4));
+    }
+
+    #[test]
+    fn framing_policies_at_different_window_shapes() {
+        let area = |policy: FramingPolicy, window: Vec2| {
+            let mut orthographic = OrthographicProjection {
+                scaling_mode: policy.scaling_mode(8.0, 8.0),
+                ..OrthographicProjection::default_3d()
+            };
+            orthographic.update(window.x, window.y);
+            orthographic.area.size()
+        };
+
+        for window in [
+            Vec2::new(1600.0, 800.0),
+            Vec2::new(600.0, 1200.0),
+            Vec2::new(700.0, 700.0),
+        ] {
+            let aspect = window.x / window.y;
+            let stretch = area(FramingPolicy::Stretch, window);
+            assert!(stretch.abs_diff_eq(Vec2::splat(8.0), 1e-4), "{stretch}");
+            let height = area(FramingPolicy::KeepHeight, window);
+            assert!(
+                height.abs_diff_eq(Vec2::new(8.0 * aspect, 8.0), 1e-4),
+                "{height}"
+            );
+            let width = area(FramingPolicy::KeepWidth, window);
+            assert!(
+                width.abs_diff_eq(Vec2::new(8.0, 8.0 / aspect), 1e-4),
+                "{width}"
+            );
+
+            // Fit keeps all of the 8 by 8 in view without stretching it.
+            let fit = area(FramingPolicy::Fit, window);
+            assert!(fit.cmpge(Vec2::splat(8.0 - 1e-4)).all(), "{fit}");
+            assert!((fit.min_element() - 8.0).abs() < 1e-4, "{fit}");
+            assert!((fit.x / fit.y - aspect).abs() < 1e-4, "{fit}");
+        }
+    }
+
+    #[test]
+    fn reset_returns_to_where_the_camera_started() {
+        let mut app = orbit_app();
+        app.add_systems(Update, reset_view.before(orbit));
+        let mut camera_settings = immediate_settings();
+        let start = Vec3::new(4.0, 1.0, 0.0);
+        camera_settings.start_at(start, 9.0);
+        let camera = spawn_camera(&mut app, camera_settings);
+
+        let mut moved = app.world_mut().get_mut::<CameraSettings>(camera).unwrap();
+        moved.set_orbit_target(Vec3::new(-2.0, 0.0, 5.0));
+        moved.set_distance(30.0);
+        for _ in 0..3 {
+            app.update();
+        }
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::KeyR);
+        app.update();
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .clear();
+        for _ in 0..60 {
+            app.update();
+        }
+
+        let settings = settings(&app, camera);
+        assert!(settings.orbit_target.abs_diff_eq(start, 1e-4));
+        assert!((settings.orbit_distance - 9.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn fit_scene_leaves_cameras_that_opt_out() {
+        let mut app = App::new();
+        app.init_resource::<Assets<Mesh>>();
+        let mesh = app
+            .world_mut()
+            .resource_mut::<Assets<Mesh>>()
+            .add(Cuboid::default());
+        app.world_mut()
+            .spawn((Mesh3d(mesh), Transform::from_xyz(10.0, 0.0, 0.0)));
+        let spawn = |app: &mut App, fit_on_startup| {
+            let mut camera_settings = CameraSettings {
+                fit_on_startup,
+                ..default()
+            };
+            camera_settings.start_at(Vec3::new(0.0, 1.0, 0.0), 12.0);
+            app.world_mut()
+                .spawn((OrbitCamera, camera_settings, Projection::default()))
+                .id()
+        };
+        let fitted = spawn(&mut app, true);
+        let placed = spawn(&mut app, false);
+
+        app.world_mut().run_system_once(fit_scene).unwrap();
+
+        let fitted = settings(&app, fitted);
+        assert!(fitted.orbit_target.abs_diff_eq(Vec3::X * 10.0, 1e-4));
+        assert_eq!(fitted.initial_target, fitted.orbit_target);
+        let placed = settings(&app, placed);
+        assert_eq!(placed.orbit_target, Vec3::new(0.0, 1.0, 0.0));
+        assert_eq!(placed.orbit_distance, 12.0);
+    }
+
+    #[test]
+    fn initial_transform_follows_euler_order() {
+        let (yaw, pitch, roll) = (0.8, -0.4, 0.3);
+        for order in [EulerRot::YXZ, EulerRot::XYZ, EulerRot::ZXY] {
+            let camera_settings = CameraSettings {
+                initial_yaw: yaw,
+                initial_pitch: pitch,
+                initial_roll: roll,
+                euler_order: order,
+                ..default()
+            };
+            let transform = initial_camera_transform(&camera_settings);
+            let expected = Quat::from_euler(order, yaw, pitch, roll);
+            assert!(transform.rotation.abs_diff_eq(expected, 1e-6), "{order:?}");
+            assert!(transform.translation.abs_diff_eq(
+                camera_settings.orbit_target - transform.forward() * camera_settings.orbit_distance,
+                1e-4
+            ));
+        }
+    }
+
+    #[test]
+    fn focus_cycle_follows_through_follow_target() {
+        let mut app = orbit_app();
+        app.init_resource::<FocusCycle>()
+            .add_systems(Update, (focus_cycle, slide).before(orbit));
+        let start = Transform::from_xyz(2.0, 0.0, 0.0);
+        let focusable = app
+            .world_mut()
+            .spawn((
+                start,
+                GlobalTransform::from(start),
+                Focusable,
+                Slide(Vec3::new(0.0, 0.0, 2.0)),
+            ))
+            .id();
+        let camera = spawn_camera(&mut app, immediate_settings());
+
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::Tab);
+        app.update();
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .clear();
+        assert_eq!(settings(&app, camera).follow_target, Some(focusable));
+
+        // Once the transition is over, the target is wherever the entity is that frame.
+        for _ in 0..60 {
+            app.update();
+        }
+        let position = transform(&app, focusable).translation;
+        assert!(position.z > 1.0);
+        assert!(settings(&app, camera)
+            .orbit_target
+            .abs_diff_eq(position, 1e-4));
+
+        // Focusing elsewhere stops following.
+        app.world_mut()
+            .get_mut::<CameraSettings>(camera)
+            .unwrap()
+            .transition_target_to(Vec3::ZERO, Easing::Linear);
+        assert_eq!(settings(&app, camera).follow_target, None);
+    }
+
+    /// A headless app with an 800 by 400 primary window and the system that sizes cameras to it,
+    /// for testing cameras laid out side by side like the example's split screen.
+    fn split_app() -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<Assets<Mesh>>()
+            .init_resource::<Assets<Image>>()
+            .init_resource::<ManualTextureViews>()
+            .add_event::<WindowCreated>()
+            .add_event::<WindowResized>()
+            .add_event::<WindowScaleFactorChanged>()
+            .add_event::<AssetEvent<Image>>()
+            .add_systems(PostUpdate, camera_system::<Projection>);
+        app.world_mut()
+            .spawn((Window::default(), PrimaryWindow))
+            .get_mut::<Window>()
+            .unwrap()
+            .resolution
+            .set(800.0, 400.0);
+        app
+    }
+
+    /// Spawn an orbit camera drawing to one half of the window, 0 for the left and 1 for the right,
+    /// with `settings`.
+    fn spawn_split_camera(app: &mut App, half: u32, settings: CameraSettings) -> Entity {
+        let transform = initial_camera_transform(&settings);
+        let camera = Camera {
+            order: half as isize,
+            viewport: Some(Viewport {
+                physical_position: UVec2::new(half * 400, 0),
+                physical_size: UVec2::new(400, 400),
+                ..default()
+            }),
+            ..default()
+        };
+        app.world_mut()
+            .spawn((
+                OrbitCamera,
+                settings,
+                camera,
+                Projection::default(),
+                transform,
+                GlobalTransform::from(transform),
+            ))
+            .id()
+    }
+
+    #[test]
+    fn cursor_ray_is_relative_to_the_viewport() {
+        let mut app = split_app();
+        let left = spawn_split_camera(&mut app, 0, CameraSettings::default());
+        let right = spawn_split_camera(&mut app, 1, CameraSettings::default());
+        app.update();
+
+        let ray = |camera, cursor| {
+            let world = app.world();
+            cursor_ray(
+                world.get::<Camera>(camera).unwrap(),
+                world.get::<GlobalTransform>(camera).unwrap(),
+                cursor,
+            )
+        };
+        // The middle of the right half is the middle of the right camera's view.
+        let forward = transform(&app, right).forward();
+        let middle = ray(right, Vec2::new(600.0, 200.0)).unwrap();
+        assert!(middle.direction.dot(*forward) > 0.9999);
+        // On the other side of the split, only the left camera casts.
+        assert!(ray(right, Vec2::new(200.0, 200.0)).is_none());
+        assert!(ray(left, Vec2::new(200.0, 200.0)).is_some());
+        assert!(ray(left, Vec2::new(600.0, 200.0)).is_none());
+    }
+
+    /// Spawn a unit cube at `position` that mesh ray casts can hit.
+    fn spawn_cube(app: &mut App, position: Vec3) -> Entity {
+        let mesh = Mesh::from(Cuboid::default());
+        let aabb = mesh.compute_aabb().unwrap();
+        let mesh = app.world_mut().resource_mut::<Assets<Mesh>>().add(mesh);
+        // Visibility is normally worked out while rendering, which a headless app doesn't do.
+        let mut view_visibility = ViewVisibility::default();
+        view_visibility.set();
+        app.world_mut()
+            .spawn((
+                Mesh3d(mesh),
+                aabb,
+                GlobalTransform::from_translation(position),
+                InheritedVisibility::VISIBLE,
+                view_visibility,
+            ))
+            .id()
+    }
+
+    /// Put the cursor at `position` in the window of a [`split_app`].
+    fn place_cursor(app: &mut App, position: Vec2) {
+        let mut windows = app.world_mut().query::<&mut Window>();
+        windows
+            .single_mut(app.world_mut())
+            .set_cursor_position(Some(position));
+    }
+
+    #[test]
+    fn double_click_focuses_in_the_right_hand_viewport() {
+        let mut app = split_app();
+        app.init_resource::<ButtonInput<MouseButton>>()
+            .init_resource::<KeyBindings>()
+            .init_resource::<FocusCycle>()
+            .insert_resource(TimeUpdateStrategy::ManualDuration(FRAME))
+            .add_systems(Update, focus_on_click);
+        let left = spawn_split_camera(&mut app, 0, CameraSettings::default());
+        let right_target = Vec3::new(10.0, 0.0, 0.0);
+        let right = spawn_split_camera(
+            &mut app,
+            1,
+            CameraSettings {
+                orbit_target: right_target,
+                ..default()
+            },
+        );
+        spawn_cube(&mut app, Vec3::ZERO);
+        let cube = spawn_cube(&mut app, right_target);
+        app.update();
+
+        // Twice, straight at the cube in the middle of the right half.
+        place_cursor(&mut app, Vec2::new(600.0, 200.0));
+        for _ in 0..2 {
+            let mut buttons = app.world_mut().resource_mut::<ButtonInput<MouseButton>>();
+            buttons.release(MouseButton::Left);
+            buttons.clear();
+            buttons.press(MouseButton::Left);
+            app.update();
+        }
+
+        assert_eq!(app.world().resource::<FocusCycle>().focused, Some(cube));
+        let transition = settings(&app, right).target_transition.unwrap();
+        assert!(transition.desired_target.abs_diff_eq(right_target, 1e-4));
+        assert!(settings(&app, left).target_transition.is_none());
+    }
+
+    #[test]
+    fn hovering_outlines_the_mesh_under_the_cursor_in_the_right_hand_viewport() {
+        let mut app = split_app();
+        // What `GizmoPlugin` would set up for the outline to be drawn with.
+        app.init_resource::<Assets<LineGizmo>>()
+            .init_gizmo_group::<DefaultGizmoConfigGroup>()
+            .add_systems(Update, highlight_hover);
+        let hover = CameraSettings {
+            highlight_hover: true,
+            ..default()
+        };
+        let left = spawn_split_camera(&mut app, 0, hover.clone());
+        let right_target = Vec3::new(10.0, 0.0, 0.0);
+        let right = spawn_split_camera(
+            &mut app,
+            1,
+            CameraSettings {
+                orbit_target: right_target,
+                ..hover
+            },
+        );
+        spawn_cube(&mut app, Vec3::ZERO);
+        let cube = spawn_cube(&mut app, right_target);
+        app.update();
+
+        place_cursor(&mut app, Vec2::new(600.0, 200.0));
+        app.update();
+
+        assert_eq!(settings(&app, right).hovered, Some(cube));
+        assert_eq!(settings(&app, left).hovered, None);
+    }
+
+    #[test]
+    fn scrolling_anchors_only_the_camera_under_the_cursor() {
+        let mut app = split_app();
+        app.init_resource::<AccumulatedMouseScroll>()
+            .add_systems(Update, zoom_to_cursor);
+        // From an earlier zoom over the left half.
+        let earlier_anchor = Vec3::new(0.0, 0.5, 0.0);
+        let left = spawn_split_camera(
+            &mut app,
+            0,
+            CameraSettings {
+                zoom_anchor: Some(earlier_anchor),
+                ..default()
+            },
+        );
+        let right_target = Vec3::new(10.0, 0.0, 0.0);
+        let right = spawn_split_camera(
+            &mut app,
+            1,
+            CameraSettings {
+                orbit_target: right_target,
+                ..default()
+            },
+        );
+        spawn_cube(&mut app, right_target);
+        app.update();
+
+        place_cursor(&mut app, Vec2::new(600.0, 200.0));
+        app.world_mut()
+            .resource_mut::<AccumulatedMouseScroll>()
+            .delta = Vec2::Y;
+        app.update();
+
+        let anchor = settings(&app, right).zoom_anchor.unwrap();
+        assert!(anchor.abs_diff_eq(right_target, 0.5 + 1e-4));
+        assert_eq!(settings(&app, left).zoom_anchor, Some(earlier_anchor));
+    }
+
+    #[test]
+    fn applying_tuning_ignores_per_camera_settings_in_the_file() {
+        let mut camera_settings = CameraSettings {
+            show_hud: true,
+            ..default()
+        };
+        let tuning = CameraSettings {
+            mode: CameraMode::FreeFly,
+            show_hud: false,
+            yaw_speed: 0.5,
+            ..default()
+        };
+        let fields = ["mode", "show_hud", "yaw_speed"].map(String::from);
+
+        camera_settings.apply_tuning(&tuning, &fields);
+
+        assert_eq!(camera_settings.mode, CameraMode::Orbit);
+        assert!(camera_settings.show_hud);
+        assert_eq!(camera_settings.yaw_speed, 0.5);
+        // These are the names that loading the file warns about.
+        let ignored: Vec<_> = CameraSettings::per_camera_fields(&fields).collect();
+        assert_eq!(ignored, ["mode", "show_hud"]);
+    }
+
+    #[test]
+    fn focus_cycle_moves_only_cameras_taking_input() {
+        let mut app = orbit_app();
+        app.init_resource::<FocusCycle>()
+            .add_systems(Update, focus_cycle.before(orbit));
+        let start = Transform::from_xyz(2.0, 0.0, 0.0);
+        let focusable = app
+            .world_mut()
+            .spawn((start, GlobalTransform::from(start), Focusable))
+            .id();
+        let active = spawn_camera(&mut app, immediate_settings());
+        // As `route_input` leaves a camera whose viewport the cursor isn't over.
+        let inactive = spawn_camera(
+            &mut app,
+            CameraSettings {
+                receives_input: false,
+                ..immediate_settings()
+            },
+        );
+
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::Tab);
+        app.update();
+
+        assert_eq!(settings(&app, active).follow_target, Some(focusable));
+        assert_eq!(settings(&app, inactive).follow_target, None);
+        assert!(settings(&app, inactive).target_transition.is_none());
+    }
+
+    #[test]
+    fn keep_target_onscreen_turns_orbit_cameras_back_toward_the_target() {
+        let mut app = split_app();
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(FRAME))
+            .add_systems(Update, keep_target_onscreen);
+        let keep_onscreen = CameraSettings {
+            keep_target_onscreen: true,
+            ..default()
+        };
+        let orbiting = spawn_split_camera(&mut app, 0, keep_onscreen.clone());
+        let flying = spawn_split_camera(
+            &mut app,
+            1,
+            CameraSettings {
+                mode: CameraMode::FreeFly,
+                ..keep_onscreen
+            },
+        );
+        app.update();
+
+        // Look a quarter turn away, leaving the target off to the side.
+        let away = Quat::from_rotation_y(FRAC_PI_2);
+        for camera in [orbiting, flying] {
+            app.world_mut()
+                .get_mut::<Transform>(camera)
+                .unwrap()
+                .rotate(away);
+        }
+        let flying_rotation = transform(&app, flying).rotation;
+        for _ in 0..60 {
+            app.update();
+        }
+
+        let camera_settings = settings(&app, orbiting);
+        let camera_transform = transform(&app, orbiting);
+        let camera = app.world().get::<Camera>(orbiting).unwrap();
+        let viewport_size = camera.logical_viewport_size().unwrap();
+        let margin = viewport_size * camera_settings.onscreen_margin;
+        let position = camera
+            .world_to_viewport(
+                &GlobalTransform::from(camera_transform),
+                camera_settings.orbit_target,
+            )
+            .unwrap();
+        assert!(Rect::from_corners(margin, viewport_size - margin).contains(position));
+        assert_eq!(transform(&app, flying).rotation, flying_rotation);
+    }
+}
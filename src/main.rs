@@ -1,79 +1,259 @@
 //! Shows how to orbit camera around a static scene using pitch, yaw, and roll.
 //!
-//! See also: `first_person_view_model` example, which does something similar but as a first-person
-//! camera view.
+//! Press V to switch the same camera to a first-person view, looking around from the orbit target
+//! instead of circling it, or C to fly around freely.
+//!
+//! Migrating from a `CameraSettings` resource: settings are now a component, so instead of
+//! passing them to `CameraControlPlugin` or inserting a resource, spawn them on the camera that
+//! should be controlled, as `setup` does below. That camera also needs the `OrbitCamera` marker.
+//!
+//! Pass `--scene <path>` with a glTF file in the assets folder to look at that instead of the
+//! cubes, for example `cargo run -- --scene models/helmet.glb`. `--distance 12` and
+//! `--target 0,1,0` start the camera at that orbit distance and target instead of framing the
+//! scene, for scripted renders, and resetting the view returns there. `--split` shows two
+//! independently controlled cameras side by side.
 
 use bevy::{
-    color::palettes::basic::{BLUE, LIME, RED},
-    input::mouse::AccumulatedMouseMotion,
-    pbr::{CascadeShadowConfigBuilder, NotShadowCaster, NotShadowReceiver},
+    pbr::CascadeShadowConfigBuilder,
     prelude::*,
-    reflect::NamedField,
+    render::camera::{ScalingMode, Viewport},
+    scene::SceneInstanceReady,
+    window::PrimaryWindow,
 };
-use bevy_mod_raycast::prelude::*;
-use std::{
-    f32::consts::{FRAC_PI_2, PI},
-    ops::Range,
+use bevy_cube::{
+    initial_camera_transform, CameraControlPlugin, CameraControlSet, CameraSettings, CameraShake,
+    Focusable, FrameAll, HideInScreenshot, KeyBindings, NavScheme, OrbitCamera,
 };
+use std::f32::consts::PI;
 
-#[derive(Debug, Resource)]
-struct CameraSettings {
-    pub orbit_distance: f32,
-    pub pitch_speed: f32,
-    // Clamp pitch to this range
-    pub pitch_range: Range<f32>,
-    pub roll_speed: f32,
-    pub yaw_speed: f32,
+/// Keeps an object turning about `axis`, which should be normalized, at `speed` radians per second.
+#[derive(Component)]
+struct Spin {
+    axis: Vec3,
+    speed: f32,
 }
 
-impl Default for CameraSettings {
-    fn default() -> Self {
-        // Limiting pitch stops some unexpected rotation past 90° up or down.
-        let pitch_limit = FRAC_PI_2 - 0.01;
-        Self {
-            // These values are completely arbitrary, chosen because they seem to produce
-            // "sensible" results for this example. Adjust as required.
-            orbit_distance: 20.0,
-            pitch_speed: 0.003,
-            pitch_range: -pitch_limit..pitch_limit,
-            roll_speed: 1.0,
-            yaw_speed: 0.004,
-        }
-    }
+/// Which half of the window a split-screen camera draws to, 0 for the left and 1 for the right.
+#[derive(Component)]
+struct SplitView(u32);
+
+/// While true, spinning objects hold still.
+#[derive(Resource, Default)]
+struct Paused(bool);
+
+/// Marks the help text, so `toggle_overlays` can find it.
+#[derive(Component)]
+struct Instructions;
+
+/// Whether the help text and HUD are hidden, and which cameras had their HUD showing before.
+#[derive(Resource, Default)]
+struct OverlaysHidden {
+    hidden: bool,
+    hud_cameras: Vec<Entity>,
 }
 
 fn main() {
     App::new()
-        .add_plugins(DefaultPlugins)
-        .init_resource::<CameraSettings>()
+        .add_plugins((DefaultPlugins, CameraControlPlugin))
+        .init_resource::<Paused>()
+        .init_resource::<OverlaysHidden>()
         .add_systems(Startup, (setup, instructions))
-        .add_systems(Update, orbit)
-        .add_systems(Update, rotate)
+        .add_systems(
+            Update,
+            (
+                // Spinning before the camera updates means it sees where objects are this frame.
+                (toggle_pause, rotate).chain().before(CameraControlSet),
+                shake_on_key,
+                cycle_nav_scheme,
+                toggle_overlays,
+                split_viewports,
+            ),
+        )
         .run();
 }
 
-/// Set up a simple 3D scene
+const USAGE: &str =
+    "usage: bevy-cube [--scene <path>] [--distance <distance>] [--target x,y,z] [--split]";
+
+/// Overrides for the starting camera and scene, from the command line.
+#[derive(Default)]
+struct Args {
+    // glTF file relative to the assets folder
+    scene: Option<String>,
+    distance: Option<f32>,
+    target: Option<Vec3>,
+    // Two cameras side by side
+    split: bool,
+}
+
+impl Args {
+    /// Parse the process arguments. Anything unrecognized or invalid prints the usage and is left
+    /// at its default, so a typo never stops the example from starting.
+    fn from_env() -> Self {
+        let mut parsed = Self::default();
+        let mut args = std::env::args().skip(1);
+        while let Some(arg) = args.next() {
+            // Only the known options take a value, so an unknown one doesn't swallow the next.
+            let valid = match arg.as_str() {
+                "--split" => {
+                    parsed.split = true;
+                    true
+                }
+                "--scene" => {
+                    parsed.scene = args.next();
+                    parsed.scene.is_some()
+                }
+                "--distance" => {
+                    parsed.distance = args
+                        .next()
+                        .and_then(|distance| distance.parse().ok())
+                        .filter(|distance: &f32| *distance > 0.0);
+                    parsed.distance.is_some()
+                }
+                "--target" => {
+                    parsed.target = args.next().and_then(|target| parse_vec3(&target));
+                    parsed.target.is_some()
+                }
+                _ => false,
+            };
+            if !valid {
+                eprintln!("ignoring invalid argument {arg:?}\n{USAGE}");
+            }
+        }
+        parsed
+    }
+}
+
+/// Parse `x,y,z` into a vector.
+fn parse_vec3(text: &str) -> Option<Vec3> {
+    let components: Vec<f32> = text
+        .split(',')
+        .map(|component| component.trim().parse().ok())
+        .collect::<Option<_>>()?;
+    match components[..] {
+        [x, y, z] => Some(Vec3::new(x, y, z)),
+        _ => None,
+    }
+}
+
+/// Set up a simple 3D scene, or the glTF scene given on the command line, with the camera where
+/// the command line puts it
 fn setup(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
+    asset_server: Res<AssetServer>,
 ) {
-    commands.spawn((
-        Name::new("Camera"),
-        Camera3dBundle {
-            projection: OrthographicProjection {
-                scaling_mode: bevy::render::camera::ScalingMode::Fixed {
+    let args = Args::from_env();
+    let mut camera_settings = CameraSettings {
+        enable_turntable: true,
+        highlight_hover: true,
+        // Stay just above the plane, so it never gets looked at from below.
+        min_camera_height: Some(0.1),
+        // Keep the target around the cubes. A loaded scene can be any size, and a target from the
+        // command line may be anywhere, so neither gets a box.
+        target_bounds: (args.scene.is_none() && args.target.is_none())
+            .then_some((Vec3::new(-3.0, 0.0, -3.0), Vec3::new(3.0, 2.0, 3.0))),
+        // Framing the scene would replace a distance or target from the command line.
+        fit_on_startup: args.distance.is_none() && args.target.is_none(),
+        scene_path: args.scene,
+        ..default()
+    };
+    let range = &camera_settings.distance_range;
+    let distance = args
+        .distance
+        .map_or(camera_settings.orbit_distance, |distance| {
+            distance.clamp(range.start, range.end)
+        });
+    let target = args.target.unwrap_or(camera_settings.orbit_target);
+    camera_settings.start_at(target, distance);
+    let fit_on_startup = camera_settings.fit_on_startup;
+    let scene_path = camera_settings.scene_path.clone();
+
+    // When split, each camera takes input over its own half of the window, and the second one
+    // starts a quarter turn round so the two show different sides.
+    let views = if args.split { 2 } else { 1 };
+    for view in 0..views {
+        let mut camera_settings = camera_settings.clone();
+        camera_settings.viewport_input = args.split;
+        camera_settings.initial_yaw += view as f32 * PI / 2.0;
+        // Starting where the settings put the camera means the first orbit update leaves it in
+        // place.
+        let camera_transform = initial_camera_transform(&camera_settings);
+        let mut camera = commands.spawn((
+            Name::new(if args.split {
+                format!("Camera {}", view + 1)
+            } else {
+                "Camera".to_string()
+            }),
+            Camera3d::default(),
+            Camera {
+                order: view as isize,
+                ..default()
+            },
+            Projection::from(OrthographicProjection {
+                scaling_mode: ScalingMode::Fixed {
                     width: 8.0,
                     height: 8.0,
                 },
                 ..OrthographicProjection::default_3d()
+            }),
+            camera_transform,
+            OrbitCamera,
+            camera_settings,
+        ));
+        if args.split {
+            camera.insert(SplitView(view));
+        }
+    }
+
+    match scene_path {
+        // Frame the scene once it is in the world, whatever its size, unless the command line
+        // says where to look.
+        Some(path) => {
+            let mut scene = commands.spawn((
+                Name::new("Scene"),
+                SceneRoot(asset_server.load(GltfAssetLabel::Scene(0).from_asset(path))),
+            ));
+            if fit_on_startup {
+                scene.observe(
+                    |_trigger: Trigger<SceneInstanceReady>, mut frame: EventWriter<FrameAll>| {
+                        frame.send(FrameAll);
+                    },
+                );
             }
-            .into(),
-            transform: Transform::from_xyz(10.0, 12.0, 16.0).looking_at(Vec3::ZERO, Vec3::Y),
+        }
+        None => spawn_cubes(&mut commands, &mut meshes, &mut materials),
+    }
+
+    commands.spawn((
+        Name::new("Light"),
+        PointLight::default(),
+        Transform::from_xyz(3.0, 2.0, 5.0),
+    ));
+
+    commands.spawn((
+        DirectionalLight {
+            illuminance: light_consts::lux::OVERCAST_DAY,
+            shadows_enabled: true,
             ..default()
         },
+        Transform::from_rotation(Quat::from_euler(EulerRot::ZYX, 0.0, PI / 2., -PI / 4.)),
+        CascadeShadowConfigBuilder {
+            first_cascade_far_bound: 7.0,
+            maximum_distance: 25.0,
+            ..default()
+        }
+        .build(),
     ));
+}
 
+/// The default scene: a plane with four cubes on it, one of them spinning
+fn spawn_cubes(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<StandardMaterial>,
+) {
     commands.spawn((
         Name::new("Plane"),
         Mesh3d(meshes.add(Plane3d::default().mesh().size(5.0, 5.0))),
@@ -90,6 +270,7 @@ fn setup(
         Mesh3d(meshes.add(Cuboid::default())),
         MeshMaterial3d(materials.add(Color::srgb(0.8, 0.7, 0.6))),
         Transform::from_xyz(1.5, 0.51, 1.5),
+        Focusable,
     ));
 
     commands.spawn((
@@ -97,6 +278,7 @@ fn setup(
         Mesh3d(meshes.add(Cuboid::default())),
         MeshMaterial3d(materials.add(Color::srgb(0.8, 0.7, 0.1))),
         Transform::from_xyz(-2.5, 0.51, 2.5),
+        Focusable,
     ));
 
     commands.spawn((
@@ -104,6 +286,7 @@ fn setup(
         Mesh3d(meshes.add(Cuboid::default())),
         MeshMaterial3d(materials.add(Color::srgb(0.8, 0.1, 0.6))),
         Transform::from_xyz(1.5, 0.51, -1.5),
+        Focusable,
     ));
 
     commands.spawn((
@@ -111,37 +294,51 @@ fn setup(
         Mesh3d(meshes.add(Cuboid::default())),
         MeshMaterial3d(materials.add(Color::srgb(0.1, 0.8, 0.6))),
         Transform::from_xyz(-1.5, 0.51, -1.5),
-    ));
-
-    commands.spawn((
-        Name::new("Light"),
-        PointLight::default(),
-        Transform::from_xyz(3.0, 2.0, 5.0),
-    ));
-
-    commands.spawn((
-        DirectionalLight {
-            illuminance: light_consts::lux::OVERCAST_DAY,
-            shadows_enabled: true,
-            ..default()
+        Focusable,
+        Spin {
+            axis: Vec3::Y,
+            speed: 1.0,
         },
-        Transform::from_rotation(Quat::from_euler(EulerRot::ZYX, 0.0, PI / 2., -PI / 4.)),
-        CascadeShadowConfigBuilder {
-            first_cascade_far_bound: 7.0,
-            maximum_distance: 25.0,
-            ..default()
-        }
-        .build(),
     ));
 }
 
 fn instructions(mut commands: Commands) {
     commands.spawn((
         Name::new("Instructions"),
+        Instructions,
+        HideInScreenshot,
         Text::new(
             "Mouse up or down: pitch\n\
             Mouse left or right: yaw\n\
-            Mouse buttons: roll",
+            Mouse buttons: roll\n\
+            Scroll wheel: zoom\n\
+            Gamepad right stick: orbit, triggers: zoom\n\
+            Numpad 4/6/8/2: orbit\n\
+            Middle mouse or arrow keys: pan\n\
+            Hold left shift: precise control\n\
+            Hold left ctrl while orbiting: snap yaw to 15° steps\n\
+            Double-click an object: focus it\n\
+            Alt+drag with left mouse: frame the objects inside the box\n\
+            Tab or Shift+Tab: step focus through the cubes\n\
+            Period: frame the focused cube\n\
+            R: reset view\n\
+            Ctrl+1-9: save view, 1-9: recall view\n\
+            F5: write saved views to disk\n\
+            F6: record a camera path, F7: play it back, F8: save it\n\
+            F12 or Print Screen: save a screenshot\n\
+            Numpad 7/1/3: top/front/right view\n\
+            P: toggle perspective\n\
+            F: frame everything\n\
+            Minus/Equals and [/]: move the near and far clip planes\n\
+            G: show axes\n\
+            F3: show camera readouts\n\
+            C: toggle flying, with WASD to move and Q/E to go down/up\n\
+            V: toggle first person\n\
+            X: shake the camera\n\
+            Space: pause spinning objects\n\
+            N: switch between Default, Maya, Blender, Unity and one-button mouse controls\n\
+            Z, with one-button controls: switch dragging between orbit and zoom\n\
+            H: hide this help and the HUD",
         ),
         Node {
             position_type: PositionType::Absolute,
@@ -152,58 +349,114 @@ fn instructions(mut commands: Commands) {
     ));
 }
 
-fn rotate(mut entities: Query<(&Name, &mut Transform)>, time: Res<Time>) {
-    for (name, mut transform) in &mut entities {
-        if name.to_string() == "Cube4" {
-            let (yaw, pitch, roll) = transform.rotation.to_euler(EulerRot::YXZ);
-            let yaw = yaw + time.delta_secs();
-
-            transform.rotation = Quat::from_euler(EulerRot::YXZ, yaw, pitch, roll);
+/// Keeps each split-screen camera drawing to its half of the window, whatever size it is.
+fn split_viewports(
+    window: Single<&Window, With<PrimaryWindow>>,
+    mut cameras: Query<(&mut Camera, &SplitView)>,
+) {
+    let size = window.physical_size();
+    // A minimized window has no size, and an empty viewport can't be rendered to.
+    if size.x < 2 || size.y == 0 {
+        return;
+    }
+    let half = size.x / 2;
+    for (mut camera, view) in &mut cameras {
+        let viewport = Viewport {
+            physical_position: UVec2::new(view.0 * half, 0),
+            physical_size: UVec2::new(if view.0 == 0 { half } else { size.x - half }, size.y),
+            ..default()
+        };
+        let unchanged = camera.viewport.as_ref().is_some_and(|current| {
+            current.physical_position == viewport.physical_position
+                && current.physical_size == viewport.physical_size
+        });
+        if !unchanged {
+            camera.viewport = Some(viewport);
         }
     }
 }
 
-fn orbit(
-    mut camera: Single<&mut Transform, With<Camera>>,
-    camera_settings: Res<CameraSettings>,
-    mouse_buttons: Res<ButtonInput<MouseButton>>,
-    mouse_motion: Res<AccumulatedMouseMotion>,
-    time: Res<Time>,
+fn toggle_pause(mut paused: ResMut<Paused>, keys: Res<ButtonInput<KeyCode>>) {
+    if keys.just_pressed(KeyCode::Space) {
+        paused.0 = !paused.0;
+    }
+}
+
+/// Each frame turns objects on from wherever they are, so unpausing carries on without a jump.
+fn rotate(mut entities: Query<(&Spin, &mut Transform)>, time: Res<Time>, paused: Res<Paused>) {
+    if paused.0 {
+        return;
+    }
+    for (spin, mut transform) in &mut entities {
+        transform.rotate(Quat::from_axis_angle(
+            spin.axis,
+            spin.speed * time.delta_secs(),
+        ));
+    }
+}
+
+/// Hides the help text and any camera HUD, or brings them back as they were.
+fn toggle_overlays(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut overlays: ResMut<OverlaysHidden>,
+    mut instructions: Query<&mut Visibility, With<Instructions>>,
+    mut cameras: Query<(Entity, &mut CameraSettings)>,
 ) {
-    let mut delta = Vec2 { x: 0.0, y: 0.0 };
-    //
-    let mut delta_roll = 0.0;
+    if !keys.just_pressed(KeyCode::KeyH) {
+        return;
+    }
+    overlays.hidden = !overlays.hidden;
+    let visibility = if overlays.hidden {
+        Visibility::Hidden
+    } else {
+        Visibility::Inherited
+    };
+    for mut instructions in &mut instructions {
+        *instructions = visibility;
+    }
 
-    if mouse_buttons.pressed(MouseButton::Left) {
-        delta = -mouse_motion.delta;
+    // The HUD follows each camera's `show_hud`, so hide it by turning that off.
+    if overlays.hidden {
+        for (entity, mut camera_settings) in &mut cameras {
+            if camera_settings.show_hud {
+                camera_settings.show_hud = false;
+                overlays.hud_cameras.push(entity);
+            }
+        }
+    } else {
+        for entity in std::mem::take(&mut overlays.hud_cameras) {
+            if let Ok((_, mut camera_settings)) = cameras.get_mut(entity) {
+                camera_settings.show_hud = true;
+            }
+        }
     }
-    if mouse_buttons.pressed(MouseButton::Right) {
-        delta_roll += 1.0;
+}
+
+fn shake_on_key(keys: Res<ButtonInput<KeyCode>>, mut shakes: EventWriter<CameraShake>) {
+    if keys.just_pressed(KeyCode::KeyX) {
+        shakes.send(CameraShake {
+            amplitude: 0.05,
+            duration: 0.5,
+        });
     }
+}
 
-    // Mouse motion is one of the few inputs that should not be multiplied by delta time,
-    // as we are already receiving the full movement since the last frame was rendered. Multiplying
-    // by delta time here would make the movement slower that it should be.
-    let delta_pitch = delta.y * camera_settings.pitch_speed;
-    let delta_yaw = delta.x * camera_settings.yaw_speed;
-
-    // Conversely, we DO need to factor in delta time for mouse button inputs.
-    delta_roll *= camera_settings.roll_speed * time.delta_secs();
-
-    // Obtain the existing pitch, yaw, and roll values from the transform.
-    let (yaw, pitch, roll) = camera.rotation.to_euler(EulerRot::YXZ);
-
-    // Establish the new yaw and pitch, preventing the pitch value from exceeding our limits.
-    let pitch = (pitch + delta_pitch).clamp(
-        camera_settings.pitch_range.start,
-        camera_settings.pitch_range.end,
-    );
-    let roll = roll + delta_roll;
-    let yaw = yaw + delta_yaw;
-    camera.rotation = Quat::from_euler(EulerRot::YXZ, yaw, pitch, roll);
-
-    // Adjust the translation to maintain the correct orientation toward the orbit target.
-    // In our example it's a static target, but this could easily be customized.
-    let target = Vec3::ZERO;
-    camera.translation = target - camera.forward() * camera_settings.orbit_distance;
+/// Switches the mouse controls to the next tool's conventions. Other bindings are left alone.
+fn cycle_nav_scheme(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut bindings: ResMut<KeyBindings>,
+    mut scheme: Local<NavScheme>,
+) {
+    if !keys.just_pressed(KeyCode::KeyN) {
+        return;
+    }
+    *scheme = match *scheme {
+        NavScheme::Default => NavScheme::Maya,
+        NavScheme::Maya => NavScheme::Blender,
+        NavScheme::Blender => NavScheme::Unity,
+        NavScheme::Unity => NavScheme::OneButton,
+        NavScheme::OneButton => NavScheme::Default,
+    };
+    bindings.apply_scheme(*scheme);
+    info!("Switched to {:?} mouse controls", *scheme);
 }